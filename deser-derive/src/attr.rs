@@ -31,6 +31,300 @@ impl RenameAll {
             Err(syn::Error::new_spanned(lit, "rename expects a string"))
         }
     }
+
+    fn apply_to_field(self, name: String) -> String {
+        match self {
+            RenameAll::LowerCase | RenameAll::SnakeCase => name,
+            RenameAll::UpperCase | RenameAll::ScreamingSnakeCase => name.to_ascii_uppercase(),
+            RenameAll::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in name.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameAll::CamelCase => {
+                let mut camel = String::new();
+                let mut capitalize = false;
+                for ch in name.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        camel.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        camel.push(ch);
+                    }
+                }
+                camel
+            }
+            RenameAll::KebabCase => name.replace("_", "-"),
+            RenameAll::ScreamingKebabCase => name.replace("_", "-").to_ascii_uppercase(),
+        }
+    }
+
+    fn apply_to_variant(self, name: String) -> String {
+        match self {
+            RenameAll::PascalCase => name,
+            RenameAll::LowerCase => name.to_ascii_lowercase(),
+            RenameAll::UpperCase => name.to_ascii_uppercase(),
+            RenameAll::CamelCase => name[..1].to_ascii_lowercase() + &name[1..],
+            RenameAll::SnakeCase
+            | RenameAll::ScreamingSnakeCase
+            | RenameAll::KebabCase
+            | RenameAll::ScreamingKebabCase => {
+                let sep = if matches!(self, RenameAll::SnakeCase | RenameAll::ScreamingSnakeCase) {
+                    '_'
+                } else {
+                    '-'
+                };
+                let upper = matches!(
+                    self,
+                    RenameAll::ScreamingKebabCase | RenameAll::ScreamingSnakeCase
+                );
+                let mut rv = String::new();
+                for (i, ch) in name.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        rv.push(sep);
+                    }
+                    rv.push(if upper {
+                        ch.to_ascii_uppercase()
+                    } else {
+                        ch.to_ascii_lowercase()
+                    });
+                }
+                rv
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Direction {
+    Serialize,
+    Deserialize,
+}
+
+pub struct Directional<T> {
+    serialize: Option<T>,
+    deserialize: Option<T>,
+}
+
+impl<T> Default for Directional<T> {
+    fn default() -> Directional<T> {
+        Directional {
+            serialize: None,
+            deserialize: None,
+        }
+    }
+}
+
+impl<T> Directional<T> {
+    fn is_set(&self) -> bool {
+        self.serialize.is_some() || self.deserialize.is_some()
+    }
+
+    pub fn get(&self, direction: Direction) -> Option<&T> {
+        match direction {
+            Direction::Serialize => self.serialize.as_ref(),
+            Direction::Deserialize => self.deserialize.as_ref(),
+        }
+    }
+
+    fn parse<F>(&mut self, meta: &syn::Meta, attr_name: &str, parse: F) -> syn::Result<()>
+    where
+        F: Fn(&syn::Lit) -> syn::Result<T>,
+        T: Clone,
+    {
+        let duplicate =
+            || syn::Error::new_spanned(meta, format!("duplicate {} attribute", attr_name));
+        match meta {
+            syn::Meta::NameValue(nv) => {
+                if self.serialize.is_some() || self.deserialize.is_some() {
+                    return Err(duplicate());
+                }
+                let value = parse(&nv.lit)?;
+                self.serialize = Some(value.clone());
+                self.deserialize = Some(value);
+            }
+            syn::Meta::List(list) => {
+                for nested in list.nested.iter() {
+                    let (slot, lit) = match nested {
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(nv))
+                            if nv.path.is_ident("serialize") =>
+                        {
+                            (&mut self.serialize, &nv.lit)
+                        }
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(nv))
+                            if nv.path.is_ident("deserialize") =>
+                        {
+                            (&mut self.deserialize, &nv.lit)
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                nested,
+                                format!(
+                                    "expected `{}(serialize = \"...\", deserialize = \"...\")`",
+                                    attr_name
+                                ),
+                            ))
+                        }
+                    };
+                    if slot.is_some() {
+                        return Err(duplicate());
+                    }
+                    *slot = Some(parse(lit)?);
+                }
+            }
+            syn::Meta::Path(_) => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    format!("expected `{} = \"...\"`", attr_name),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum EnumRepr {
+    Name,
+    Int,
+    IntOrName,
+    NameOrInt,
+}
+
+impl EnumRepr {
+    fn parse(lit: &syn::Lit) -> Result<EnumRepr, syn::Error> {
+        match get_lit_str("repr", lit)?.as_str() {
+            "name" => Ok(EnumRepr::Name),
+            "int" => Ok(EnumRepr::Int),
+            "int_or_name" => Ok(EnumRepr::IntOrName),
+            "name_or_int" => Ok(EnumRepr::NameOrInt),
+            _ => Err(syn::Error::new_spanned(
+                lit,
+                "expected one of \"name\", \"int\", \"int_or_name\" or \"name_or_int\"",
+            )),
+        }
+    }
+
+    pub fn serialize_as_int(self) -> bool {
+        matches!(self, EnumRepr::Int | EnumRepr::IntOrName)
+    }
+
+    pub fn accepts_int(self) -> bool {
+        self != EnumRepr::Name
+    }
+
+    pub fn accepts_name(self) -> bool {
+        self != EnumRepr::Int
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum MatchKeys {
+    Exact,
+    CaseInsensitive,
+    Normalized,
+}
+
+impl MatchKeys {
+    fn parse(lit: &syn::Lit) -> Result<MatchKeys, syn::Error> {
+        match get_lit_str("match_keys", lit)?.as_str() {
+            "exact" => Ok(MatchKeys::Exact),
+            "case_insensitive" => Ok(MatchKeys::CaseInsensitive),
+            "normalized" => Ok(MatchKeys::Normalized),
+            _ => Err(syn::Error::new_spanned(
+                lit,
+                "expected one of \"exact\", \"case_insensitive\" or \"normalized\"",
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MatchKeys::Exact => "exact",
+            MatchKeys::CaseInsensitive => "case_insensitive",
+            MatchKeys::Normalized => "normalized",
+        }
+    }
+
+    pub fn fold(self, key: &str) -> String {
+        match self {
+            MatchKeys::Exact => key.to_string(),
+            MatchKeys::CaseInsensitive => key.to_lowercase(),
+            MatchKeys::Normalized => key
+                .chars()
+                .filter(|&c| c != '_' && c != '-')
+                .collect::<String>()
+                .to_lowercase(),
+        }
+    }
+}
+
+pub enum Validator {
+    Custom(syn::ExprPath),
+    Range(Option<syn::Lit>, Option<syn::Lit>),
+    Length(Option<syn::Lit>, Option<syn::Lit>),
+    NonEmpty,
+}
+
+fn parse_validator_bounds(
+    list: &syn::MetaList,
+) -> syn::Result<(Option<syn::Lit>, Option<syn::Lit>)> {
+    let mut min = None;
+    let mut max = None;
+    for nested in &list.nested {
+        match nested {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(nv))
+                if nv.path.is_ident("min") && min.is_none() =>
+            {
+                min = Some(nv.lit.clone());
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(nv))
+                if nv.path.is_ident("max") && max.is_none() =>
+            {
+                max = Some(nv.lit.clone());
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    nested,
+                    "expected a single min and max argument",
+                ))
+            }
+        }
+    }
+    if min.is_none() && max.is_none() {
+        return Err(syn::Error::new_spanned(
+            list,
+            "expected at least one of min and max",
+        ));
+    }
+    Ok((min, max))
+}
+
+/// Range bounds are cast to the field type with `as` which only works for primitives.
+fn is_primitive_number(ty: &syn::Type) -> bool {
+    const NUMBERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+        "f32", "f64",
+    ];
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .is_some_and(|ident| NUMBERS.iter().any(|x| ident == x)),
+        _ => false,
+    }
 }
 
 #[derive(Clone)]
@@ -41,10 +335,20 @@ pub enum TypeDefault {
 
 pub struct ContainerAttrs<'a> {
     ident: &'a syn::Ident,
-    rename: Option<String>,
-    rename_all: Option<RenameAll>,
+    rename: Directional<String>,
+    rename_all: Directional<RenameAll>,
     default: Option<TypeDefault>,
     skip_serializing_optionals: bool,
+    allow_duplicate_fields: bool,
+    deny_unknown_fields: bool,
+    match_keys: Option<MatchKeys>,
+    validate: Option<syn::ExprPath>,
+    repr: Option<EnumRepr>,
+    crate_path: Option<syn::Path>,
+    remote: Option<syn::Path>,
+    as_seq: bool,
+    as_string: bool,
+    bound: Directional<Vec<syn::WherePredicate>>,
 }
 
 pub fn get_meta_items(attr: &syn::Attribute) -> syn::Result<Vec<syn::NestedMeta>> {
@@ -92,36 +396,70 @@ fn parse_lit_into_expr_path(attr_name: &str, lit: &syn::Lit) -> syn::Result<syn:
     syn::parse2(respan(token_stream, lit.span()))
 }
 
+fn parse_lit_into_path(attr_name: &str, lit: &syn::Lit) -> syn::Result<syn::Path> {
+    let string = get_lit_str(attr_name, lit)?;
+    let token_stream = syn::parse_str(&string)?;
+    syn::parse2(respan(token_stream, lit.span()))
+}
+
+fn parse_lit_into_where(attr_name: &str, lit: &syn::Lit) -> syn::Result<Vec<syn::WherePredicate>> {
+    let string = get_lit_str(attr_name, lit)?;
+    if string.is_empty() {
+        return Ok(Vec::new());
+    }
+    syn::parse_str::<syn::WhereClause>(&format!("where {}", string))
+        .map(|clause| clause.predicates.into_iter().collect())
+        .map_err(|err| syn::Error::new_spanned(lit, err))
+}
+
+fn parse_lit_into_groups(lit: &syn::Lit) -> syn::Result<Vec<String>> {
+    let string = get_lit_str("groups", lit)?;
+    let groups = string
+        .split(',')
+        .map(|group| group.trim().to_string())
+        .collect::<Vec<_>>();
+    if groups.iter().any(|group| group.is_empty()) {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "groups expects a comma separated list of group names",
+        ));
+    }
+    Ok(groups)
+}
+
 impl<'a> ContainerAttrs<'a> {
     pub fn of(input: &'a syn::DeriveInput) -> syn::Result<ContainerAttrs<'a>> {
         let mut rv = ContainerAttrs {
             ident: &input.ident,
-            rename: None,
-            rename_all: None,
+            rename: Directional::default(),
+            rename_all: Directional::default(),
             default: None,
             skip_serializing_optionals: false,
+            allow_duplicate_fields: false,
+            deny_unknown_fields: false,
+            match_keys: None,
+            validate: None,
+            repr: None,
+            crate_path: None,
+            remote: None,
+            as_seq: false,
+            as_string: false,
+            bound: Directional::default(),
         };
 
         for meta_item in input.attrs.iter().flat_map(get_meta_items).flatten() {
             if let syn::NestedMeta::Meta(meta) = meta_item {
                 match &meta {
-                    syn::Meta::NameValue(nv) if nv.path.is_ident("rename_all") => {
-                        if rv.rename_all.is_some() {
-                            return Err(syn::Error::new_spanned(
-                                meta,
-                                "duplicate rename_all attribute",
-                            ));
-                        }
-                        rv.rename_all = Some(RenameAll::parse(&nv.lit)?);
+                    syn::Meta::NameValue(_) | syn::Meta::List(_)
+                        if meta.path().is_ident("rename_all") =>
+                    {
+                        rv.rename_all.parse(&meta, "rename_all", RenameAll::parse)?;
                     }
-                    syn::Meta::NameValue(nv) if nv.path.is_ident("rename") => {
-                        if rv.rename.is_some() {
-                            return Err(syn::Error::new_spanned(
-                                meta,
-                                "duplicate rename attribute",
-                            ));
-                        }
-                        rv.rename = Some(get_lit_str("rename", &nv.lit)?);
+                    syn::Meta::NameValue(_) | syn::Meta::List(_)
+                        if meta.path().is_ident("rename") =>
+                    {
+                        rv.rename
+                            .parse(&meta, "rename", |lit| get_lit_str("rename", lit))?;
                     }
                     syn::Meta::Path(path) if path.is_ident("default") => {
                         if rv.default.is_some() {
@@ -141,6 +479,24 @@ impl<'a> ContainerAttrs<'a> {
                         }
                         rv.skip_serializing_optionals = true;
                     }
+                    syn::Meta::Path(path) if path.is_ident("allow_duplicate_fields") => {
+                        if rv.allow_duplicate_fields {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate allow_duplicate_fields attribute",
+                            ));
+                        }
+                        rv.allow_duplicate_fields = true;
+                    }
+                    syn::Meta::Path(path) if path.is_ident("deny_unknown_fields") => {
+                        if rv.deny_unknown_fields {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate deny_unknown_fields attribute",
+                            ));
+                        }
+                        rv.deny_unknown_fields = true;
+                    }
                     syn::Meta::NameValue(nv) if nv.path.is_ident("default") => {
                         if rv.default.is_some() {
                             return Err(syn::Error::new_spanned(
@@ -152,6 +508,69 @@ impl<'a> ContainerAttrs<'a> {
                             "default", &nv.lit,
                         )?));
                     }
+                    syn::Meta::NameValue(nv) if nv.path.is_ident("repr") => {
+                        if rv.repr.is_some() {
+                            return Err(syn::Error::new_spanned(meta, "duplicate repr attribute"));
+                        }
+                        rv.repr = Some(EnumRepr::parse(&nv.lit)?);
+                    }
+                    syn::Meta::NameValue(nv) if nv.path.is_ident("match_keys") => {
+                        if rv.match_keys.is_some() {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate match_keys attribute",
+                            ));
+                        }
+                        rv.match_keys = Some(MatchKeys::parse(&nv.lit)?);
+                    }
+                    syn::Meta::NameValue(nv) if nv.path.is_ident("validate") => {
+                        if rv.validate.is_some() {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate validate attribute",
+                            ));
+                        }
+                        rv.validate = Some(parse_lit_into_expr_path("validate", &nv.lit)?);
+                    }
+                    syn::Meta::NameValue(nv) if nv.path.is_ident("crate") => {
+                        if rv.crate_path.is_some() {
+                            return Err(syn::Error::new_spanned(meta, "duplicate crate attribute"));
+                        }
+                        rv.crate_path = Some(parse_lit_into_path("crate", &nv.lit)?);
+                    }
+                    syn::Meta::Path(path) if path.is_ident("as_string") => {
+                        if rv.as_string {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate as_string attribute",
+                            ));
+                        }
+                        rv.as_string = true;
+                    }
+                    syn::Meta::Path(path) if path.is_ident("as_seq") => {
+                        if rv.as_seq {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate as_seq attribute",
+                            ));
+                        }
+                        rv.as_seq = true;
+                    }
+                    syn::Meta::NameValue(nv) if nv.path.is_ident("remote") => {
+                        if rv.remote.is_some() {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate remote attribute",
+                            ));
+                        }
+                        rv.remote = Some(parse_lit_into_path("remote", &nv.lit)?);
+                    }
+                    syn::Meta::NameValue(_) | syn::Meta::List(_)
+                        if meta.path().is_ident("bound") =>
+                    {
+                        rv.bound
+                            .parse(&meta, "bound", |lit| parse_lit_into_where("bound", lit))?;
+                    }
                     _ => return Err(syn::Error::new_spanned(meta, "unsupported attribute")),
                 }
             } else {
@@ -159,57 +578,96 @@ impl<'a> ContainerAttrs<'a> {
             }
         }
 
+        if rv.as_seq {
+            let is_named_struct = matches!(
+                input.data,
+                syn::Data::Struct(syn::DataStruct {
+                    fields: syn::Fields::Named(_),
+                    ..
+                })
+            );
+            if !is_named_struct || rv.remote.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "as_seq is only supported for structs with named fields and cannot be combined with remote",
+                ));
+            }
+            if let syn::Data::Struct(ref data) = input.data {
+                if data.fields.is_empty() || data.fields.len() > 12 {
+                    return Err(syn::Error::new_spanned(
+                        &data.fields,
+                        "as_seq is only supported for structs with 1 to 12 fields",
+                    ));
+                }
+            }
+            rv.ensure_no_keyed_attrs(input, "as_seq")?;
+        }
+
+        if rv.as_string {
+            if rv.as_seq || rv.remote.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "as_string cannot be combined with as_seq or remote",
+                ));
+            }
+            rv.ensure_no_keyed_attrs(input, "as_string")?;
+        }
+
+        if let Some(ref remote) = rv.remote {
+            let is_named_struct = matches!(
+                input.data,
+                syn::Data::Struct(syn::DataStruct {
+                    fields: syn::Fields::Named(_),
+                    ..
+                })
+            );
+            if !is_named_struct || !input.generics.params.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    remote,
+                    "remote is only supported for structs with named fields and no generics",
+                ));
+            }
+        }
+
         Ok(rv)
     }
 
-    pub fn container_name(&self) -> String {
-        match self.rename {
-            Some(ref name) => name.clone(),
+    /// Rejects attributes that only have an effect on keyed representations.
+    fn ensure_no_keyed_attrs(&self, input: &syn::DeriveInput, attr_name: &str) -> syn::Result<()> {
+        let keyed_attrs = [
+            ("rename_all", self.rename_all.is_set()),
+            ("default", self.default.is_some()),
+            (
+                "skip_serializing_optionals",
+                self.skip_serializing_optionals,
+            ),
+            ("allow_duplicate_fields", self.allow_duplicate_fields),
+            ("deny_unknown_fields", self.deny_unknown_fields),
+            ("match_keys", self.match_keys.is_some()),
+            ("validate", self.validate.is_some()),
+            ("repr", self.repr.is_some()),
+        ];
+        match keyed_attrs.iter().find(|(_, is_set)| *is_set) {
+            Some((name, _)) => Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("{} cannot be combined with {}", attr_name, name),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub fn container_name(&self, direction: Direction) -> String {
+        match self.rename.get(direction) {
+            Some(name) => name.clone(),
             None => self.ident.to_string(),
         }
     }
 
-    pub fn get_field_name(&self, field: &syn::Field) -> String {
+    pub fn get_field_name(&self, field: &syn::Field, direction: Direction) -> String {
         let name = field.ident.as_ref().unwrap().to_string();
-        if let Some(rename_all) = self.rename_all {
-            match rename_all {
-                RenameAll::LowerCase | RenameAll::SnakeCase => name,
-                RenameAll::UpperCase | RenameAll::ScreamingSnakeCase => name.to_ascii_uppercase(),
-                RenameAll::PascalCase => {
-                    let mut pascal = String::new();
-                    let mut capitalize = true;
-                    for ch in name.chars() {
-                        if ch == '_' {
-                            capitalize = true;
-                        } else if capitalize {
-                            pascal.push(ch.to_ascii_uppercase());
-                            capitalize = false;
-                        } else {
-                            pascal.push(ch);
-                        }
-                    }
-                    pascal
-                }
-                RenameAll::CamelCase => {
-                    let mut camel = String::new();
-                    let mut capitalize = false;
-                    for ch in name.chars() {
-                        if ch == '_' {
-                            capitalize = true;
-                        } else if capitalize {
-                            camel.push(ch.to_ascii_uppercase());
-                            capitalize = false;
-                        } else {
-                            camel.push(ch);
-                        }
-                    }
-                    camel
-                }
-                RenameAll::KebabCase => name.replace("_", "-"),
-                RenameAll::ScreamingKebabCase => name.replace("_", "-").to_ascii_uppercase(),
-            }
-        } else {
-            name
+        match self.rename_all.get(direction) {
+            Some(rename_all) => rename_all.apply_to_field(name),
+            None => name,
         }
     }
 
@@ -221,46 +679,53 @@ impl<'a> ContainerAttrs<'a> {
         self.skip_serializing_optionals
     }
 
-    pub fn get_variant_name(&self, variant: &syn::Variant) -> String {
+    pub fn allow_duplicate_fields(&self) -> bool {
+        self.allow_duplicate_fields
+    }
+
+    pub fn deny_unknown_fields(&self) -> bool {
+        self.deny_unknown_fields
+    }
+
+    pub fn repr(&self) -> EnumRepr {
+        self.repr.unwrap_or(EnumRepr::Name)
+    }
+
+    pub fn validate(&self) -> Option<&syn::ExprPath> {
+        self.validate.as_ref()
+    }
+
+    pub fn match_keys(&self) -> MatchKeys {
+        self.match_keys.unwrap_or(MatchKeys::Exact)
+    }
+
+    pub fn as_string(&self) -> bool {
+        self.as_string
+    }
+
+    pub fn as_seq(&self) -> bool {
+        self.as_seq
+    }
+
+    pub fn remote(&self) -> Option<&syn::Path> {
+        self.remote.as_ref()
+    }
+
+    pub fn crate_path(&self) -> syn::Path {
+        self.crate_path
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote!(::deser))
+    }
+
+    pub fn bound(&self, direction: Direction) -> Option<&[syn::WherePredicate]> {
+        self.bound.get(direction).map(|x| &x[..])
+    }
+
+    pub fn get_variant_name(&self, variant: &syn::Variant, direction: Direction) -> String {
         let name = variant.ident.to_string();
-        if let Some(rename_all) = self.rename_all {
-            match rename_all {
-                RenameAll::PascalCase => name,
-                RenameAll::LowerCase => name.to_ascii_lowercase(),
-                RenameAll::UpperCase => name.to_ascii_uppercase(),
-                RenameAll::CamelCase => name[..1].to_ascii_lowercase() + &name[1..],
-                RenameAll::SnakeCase
-                | RenameAll::ScreamingSnakeCase
-                | RenameAll::KebabCase
-                | RenameAll::ScreamingKebabCase => {
-                    let sep = if matches!(
-                        rename_all,
-                        RenameAll::SnakeCase | RenameAll::ScreamingSnakeCase
-                    ) {
-                        '_'
-                    } else {
-                        '-'
-                    };
-                    let upper = matches!(
-                        rename_all,
-                        RenameAll::ScreamingKebabCase | RenameAll::ScreamingSnakeCase
-                    );
-                    let mut rv = String::new();
-                    for (i, ch) in name.char_indices() {
-                        if i > 0 && ch.is_uppercase() {
-                            rv.push(sep);
-                        }
-                        rv.push(if upper {
-                            ch.to_ascii_uppercase()
-                        } else {
-                            ch.to_ascii_lowercase()
-                        });
-                    }
-                    rv
-                }
-            }
-        } else {
-            name
+        match self.rename_all.get(direction) {
+            Some(rename_all) => rename_all.apply_to_variant(name),
+            None => name,
         }
     }
 }
@@ -275,35 +740,42 @@ pub fn ensure_no_field_attrs(field: &syn::Field) -> syn::Result<()> {
 
 pub struct FieldAttrs<'a> {
     field: &'a syn::Field,
-    rename: Option<String>,
+    rename: Directional<String>,
     aliases: Vec<String>,
     default: Option<TypeDefault>,
     flatten: bool,
+    flatten_prefix: Option<String>,
     skip_serializing_if: Option<syn::ExprPath>,
+    validators: Vec<Validator>,
+    with: Option<syn::ExprPath>,
+    with_string: bool,
+    groups: Vec<String>,
 }
 
 impl<'a> FieldAttrs<'a> {
     pub fn of(field: &'a syn::Field) -> syn::Result<FieldAttrs<'a>> {
         let mut rv = FieldAttrs {
             field,
-            rename: None,
+            rename: Directional::default(),
             aliases: Vec::new(),
             default: None,
             flatten: false,
+            flatten_prefix: None,
             skip_serializing_if: None,
+            validators: Vec::new(),
+            with: None,
+            with_string: false,
+            groups: Vec::new(),
         };
 
         for meta_item in field.attrs.iter().flat_map(get_meta_items).flatten() {
             if let syn::NestedMeta::Meta(meta) = meta_item {
                 match &meta {
-                    syn::Meta::NameValue(nv) if nv.path.is_ident("rename") => {
-                        if rv.rename.is_some() {
-                            return Err(syn::Error::new_spanned(
-                                meta,
-                                "duplicate rename attribute",
-                            ));
-                        }
-                        rv.rename = Some(get_lit_str("rename", &nv.lit)?);
+                    syn::Meta::NameValue(_) | syn::Meta::List(_)
+                        if meta.path().is_ident("rename") =>
+                    {
+                        rv.rename
+                            .parse(&meta, "rename", |lit| get_lit_str("rename", lit))?;
                     }
                     syn::Meta::NameValue(nv) if nv.path.is_ident("alias") => {
                         rv.aliases.push(get_lit_str("alias", &nv.lit)?);
@@ -337,6 +809,83 @@ impl<'a> FieldAttrs<'a> {
                         }
                         rv.flatten = true;
                     }
+                    syn::Meta::List(list) if list.path.is_ident("flatten") => {
+                        if rv.flatten {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate flatten attribute",
+                            ));
+                        }
+                        rv.flatten = true;
+                        for nested in &list.nested {
+                            match nested {
+                                syn::NestedMeta::Meta(syn::Meta::NameValue(nv))
+                                    if nv.path.is_ident("prefix") =>
+                                {
+                                    if rv.flatten_prefix.is_some() {
+                                        return Err(syn::Error::new_spanned(
+                                            nv,
+                                            "duplicate prefix attribute",
+                                        ));
+                                    }
+                                    rv.flatten_prefix = Some(get_lit_str("prefix", &nv.lit)?);
+                                }
+                                _ => {
+                                    return Err(syn::Error::new_spanned(
+                                        nested,
+                                        "unsupported flatten attribute",
+                                    ))
+                                }
+                            }
+                        }
+                    }
+                    syn::Meta::NameValue(nv) if nv.path.is_ident("validate") => {
+                        rv.validators
+                            .push(Validator::Custom(parse_lit_into_expr_path(
+                                "validate", &nv.lit,
+                            )?));
+                    }
+                    syn::Meta::List(list) if list.path.is_ident("range") => {
+                        if !is_primitive_number(&field.ty) {
+                            return Err(syn::Error::new_spanned(
+                                &field.ty,
+                                "range is only supported on primitive integer and float fields",
+                            ));
+                        }
+                        let (min, max) = parse_validator_bounds(list)?;
+                        rv.validators.push(Validator::Range(min, max));
+                    }
+                    syn::Meta::List(list) if list.path.is_ident("length") => {
+                        let (min, max) = parse_validator_bounds(list)?;
+                        rv.validators.push(Validator::Length(min, max));
+                    }
+                    syn::Meta::Path(path) if path.is_ident("non_empty") => {
+                        rv.validators.push(Validator::NonEmpty);
+                    }
+                    syn::Meta::Path(path) if path.is_ident("with_string") => {
+                        if rv.with_string {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate with_string attribute",
+                            ));
+                        }
+                        rv.with_string = true;
+                    }
+                    syn::Meta::NameValue(nv) if nv.path.is_ident("groups") => {
+                        if !rv.groups.is_empty() {
+                            return Err(syn::Error::new_spanned(
+                                meta,
+                                "duplicate groups attribute",
+                            ));
+                        }
+                        rv.groups = parse_lit_into_groups(&nv.lit)?;
+                    }
+                    syn::Meta::NameValue(nv) if nv.path.is_ident("with") => {
+                        if rv.with.is_some() {
+                            return Err(syn::Error::new_spanned(meta, "duplicate with attribute"));
+                        }
+                        rv.with = Some(parse_lit_into_expr_path("with", &nv.lit)?);
+                    }
                     syn::Meta::NameValue(nv) if nv.path.is_ident("skip_serializing_if") => {
                         if rv.skip_serializing_if.is_some() {
                             return Err(syn::Error::new_spanned(
@@ -360,6 +909,18 @@ impl<'a> FieldAttrs<'a> {
                 "cannot combine flatten and default",
             ));
         }
+        if rv.flatten && (rv.with.is_some() || rv.with_string) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "cannot combine flatten and with",
+            ));
+        }
+        if rv.with.is_some() && rv.with_string {
+            return Err(syn::Error::new_spanned(
+                field,
+                "cannot combine with and with_string",
+            ));
+        }
 
         Ok(rv)
     }
@@ -368,11 +929,11 @@ impl<'a> FieldAttrs<'a> {
         self.field
     }
 
-    pub fn name(&self, container_attrs: &ContainerAttrs) -> Cow<'_, str> {
+    pub fn name(&self, container_attrs: &ContainerAttrs, direction: Direction) -> Cow<'_, str> {
         self.rename
-            .as_deref()
-            .map(Cow::Borrowed)
-            .unwrap_or_else(|| container_attrs.get_field_name(self.field).into())
+            .get(direction)
+            .map(|x| Cow::Borrowed(x.as_str()))
+            .unwrap_or_else(|| container_attrs.get_field_name(self.field, direction).into())
     }
 
     pub fn aliases(&self) -> &[String] {
@@ -387,40 +948,65 @@ impl<'a> FieldAttrs<'a> {
         self.flatten
     }
 
+    pub fn flatten_prefix(&self) -> Option<&str> {
+        self.flatten_prefix.as_deref()
+    }
+
     pub fn skip_serializing_if(&self) -> Option<&syn::ExprPath> {
         self.skip_serializing_if.as_ref()
     }
+
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    pub fn with(&self, krate: &syn::Path) -> Option<syn::ExprPath> {
+        if self.with_string {
+            Some(syn::parse_quote!(#krate::__derive::as_string))
+        } else {
+            self.with.clone()
+        }
+    }
+
+    pub fn validators(&self) -> &[Validator] {
+        &self.validators
+    }
 }
 
 pub struct EnumVariantAttrs<'a> {
     variant: &'a syn::Variant,
-    rename: Option<String>,
+    rename: Directional<String>,
     aliases: Vec<String>,
+    other: bool,
 }
 
 impl<'a> EnumVariantAttrs<'a> {
     pub fn of(variant: &'a syn::Variant) -> syn::Result<EnumVariantAttrs<'a>> {
         let mut rv = EnumVariantAttrs {
             variant,
-            rename: None,
+            rename: Directional::default(),
             aliases: Vec::new(),
+            other: false,
         };
 
         for meta_item in variant.attrs.iter().flat_map(get_meta_items).flatten() {
             if let syn::NestedMeta::Meta(meta) = meta_item {
                 match &meta {
-                    syn::Meta::NameValue(nv) if nv.path.is_ident("rename") => {
-                        if rv.rename.is_some() {
-                            return Err(syn::Error::new_spanned(
-                                meta,
-                                "duplicate rename attribute",
-                            ));
-                        }
-                        rv.rename = Some(get_lit_str("rename", &nv.lit)?);
+                    syn::Meta::NameValue(_) | syn::Meta::List(_)
+                        if meta.path().is_ident("rename") =>
+                    {
+                        rv.rename
+                            .parse(&meta, "rename", |lit| get_lit_str("rename", lit))?;
                     }
                     syn::Meta::NameValue(nv) if nv.path.is_ident("alias") => {
                         rv.aliases.push(get_lit_str("alias", &nv.lit)?);
                     }
+                    syn::Meta::Path(path) if path.is_ident("other") => {
+                        if rv.other {
+                            return Err(syn::Error::new_spanned(meta, "duplicate other attribute"));
+                        }
+                        rv.other = true;
+                    }
                     _ => return Err(syn::Error::new_spanned(meta, "unsupported attribute")),
                 }
             } else {
@@ -428,6 +1014,17 @@ impl<'a> EnumVariantAttrs<'a> {
             }
         }
 
+        match variant.fields {
+            syn::Fields::Unit => {}
+            syn::Fields::Unnamed(ref fields) if rv.other && fields.unnamed.len() == 1 => {}
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "Invalid variant: only simple enum variants without fields are supported",
+                ))
+            }
+        }
+
         Ok(rv)
     }
 
@@ -435,14 +1032,47 @@ impl<'a> EnumVariantAttrs<'a> {
         self.variant
     }
 
-    pub fn name(&self, container_attrs: &ContainerAttrs) -> Cow<'_, str> {
+    pub fn name(&self, container_attrs: &ContainerAttrs, direction: Direction) -> Cow<'_, str> {
         self.rename
-            .as_deref()
-            .map(Cow::Borrowed)
-            .unwrap_or_else(|| container_attrs.get_variant_name(self.variant).into())
+            .get(direction)
+            .map(|x| Cow::Borrowed(x.as_str()))
+            .unwrap_or_else(|| {
+                container_attrs
+                    .get_variant_name(self.variant, direction)
+                    .into()
+            })
     }
 
     pub fn aliases(&self) -> &[String] {
         &self.aliases
     }
+
+    pub fn other(&self) -> bool {
+        self.other
+    }
+
+    pub fn captures_other(&self) -> bool {
+        self.other && !matches!(self.variant.fields, syn::Fields::Unit)
+    }
+}
+
+pub fn ensure_valid_other_variant(
+    container_attrs: &ContainerAttrs,
+    attrs: &[EnumVariantAttrs],
+) -> syn::Result<()> {
+    if let Some(second) = attrs.iter().filter(|x| x.other()).nth(1) {
+        return Err(syn::Error::new_spanned(
+            second.variant(),
+            "only one variant can be marked as other",
+        ));
+    }
+    if container_attrs.repr().accepts_int() {
+        if let Some(capturing) = attrs.iter().find(|x| x.captures_other()) {
+            return Err(syn::Error::new_spanned(
+                capturing.variant(),
+                "other variants with a value cannot be combined with integer representations",
+            ));
+        }
+    }
+    Ok(())
 }
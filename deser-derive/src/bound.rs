@@ -33,6 +33,38 @@ pub fn with_lifetime_bound(generics: &syn::Generics, lifetime: &str) -> syn::Gen
     }
 }
 
+pub fn where_clause_with_custom_bound(
+    generics: &syn::Generics,
+    predicates: Option<&[syn::WherePredicate]>,
+    bound: TokenStream,
+) -> syn::WhereClause {
+    match predicates {
+        Some(predicates) => {
+            let mut generics = generics.clone();
+            generics
+                .make_where_clause()
+                .predicates
+                .extend(predicates.iter().cloned());
+            generics.where_clause.unwrap()
+        }
+        None => where_clause_with_bound(generics, bound),
+    }
+}
+
+pub fn where_clause_with_predicates(
+    generics: &syn::Generics,
+    custom: Option<&[syn::WherePredicate]>,
+    predicates: Vec<syn::WherePredicate>,
+) -> syn::WhereClause {
+    let mut generics = generics.clone();
+    let where_clause = generics.make_where_clause();
+    match custom {
+        Some(custom) => where_clause.predicates.extend(custom.iter().cloned()),
+        None => where_clause.predicates.extend(predicates),
+    }
+    generics.where_clause.unwrap()
+}
+
 pub fn where_clause_with_bound(generics: &syn::Generics, bound: TokenStream) -> syn::WhereClause {
     let new_predicates = generics
         .type_params()
@@ -1,14 +1,20 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 
 use crate::attr::{
-    ensure_no_field_attrs, ContainerAttrs, EnumVariantAttrs, FieldAttrs, TypeDefault,
+    ensure_no_field_attrs, ensure_valid_other_variant, ContainerAttrs, Direction, EnumVariantAttrs,
+    FieldAttrs, MatchKeys, TypeDefault, Validator,
+};
+use crate::bound::{
+    where_clause_with_custom_bound, where_clause_with_predicates, with_lifetime_bound,
 };
-use crate::bound::{where_clause_with_bound, with_lifetime_bound};
 
 pub fn derive_deserialize(input: &mut syn::DeriveInput) -> syn::Result<TokenStream> {
+    if ContainerAttrs::of(input)?.as_string() {
+        return derive_as_string(input);
+    }
     match &input.data {
         syn::Data::Struct(syn::DataStruct {
             fields: syn::Fields::Named(fields),
@@ -32,7 +38,11 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
     );
 
     let container_attrs = ContainerAttrs::of(input)?;
-    let type_name = container_attrs.container_name();
+    if container_attrs.as_seq() {
+        return derive_seq_struct(input, fields, &container_attrs);
+    }
+    let krate = container_attrs.crate_path();
+    let type_name = container_attrs.container_name(Direction::Deserialize);
     let attrs = fields
         .named
         .iter()
@@ -54,11 +64,11 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
             let ty = &f.field().ty;
             if f.flatten() {
                 quote! {
-                    ::deser::de::OwnedSink<#ty>
+                    #krate::de::OwnedSink<#ty>
                 }
             } else {
                 quote! {
-                    ::deser::__derive::Option<#ty>
+                    #krate::__derive::Option<#ty>
                 }
             }
         })
@@ -68,22 +78,21 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
         .map(|f| {
             if f.flatten() {
                 quote! {
-                    ::deser::de::OwnedSink::deserialize()
-                }
-            } else if f.default().is_some() {
-                quote! {
-                    ::deser::__derive::None
+                    #krate::de::OwnedSink::deserialize()
                 }
             } else {
                 quote! {
-                    ::deser::de::Deserialize::__private_initial_value()
+                    #krate::__derive::None
                 }
             }
         })
         .collect::<Vec<_>>();
 
-    let mut seen_names = HashSet::new();
+    let check_duplicates = !container_attrs.allow_duplicate_fields();
+    let match_keys = container_attrs.match_keys();
+    let mut seen_names = HashMap::<String, (usize, String)>::new();
     let mut first_duplicate_name = None;
+    let mut seen_count = 0usize;
     let matcher = attrs
         .iter()
         .zip(sink_fieldname.iter())
@@ -91,50 +100,116 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
             if x.flatten() {
                 return None;
             }
-
-            let name = x.name(&container_attrs).to_string();
-            if first_duplicate_name.is_none() && seen_names.contains(&name) {
-                first_duplicate_name = Some((name.clone(), x.field()));
-            }
-            seen_names.insert(name.clone());
-
-            let mut rv = quote! { #name };
-            for alias in x.aliases() {
-                let alias = alias.clone();
-                if first_duplicate_name.is_none() && seen_names.contains(&alias) {
-                    first_duplicate_name = Some((alias.clone(), x.field()));
+            let seen_index = seen_count;
+            seen_count += 1;
+
+            let name = x.name(&container_attrs, Direction::Deserialize).to_string();
+            let mut patterns = Vec::new();
+            for key in std::iter::once(&name).chain(x.aliases()) {
+                let folded = match_keys.fold(key);
+                match seen_names.get(&folded) {
+                    Some((index, _)) if *index == seen_index => continue,
+                    Some((_, other)) if first_duplicate_name.is_none() => {
+                        first_duplicate_name = Some((key.clone(), other.clone(), x.field()));
+                    }
+                    _ => {}
                 }
-                seen_names.insert(alias.clone());
-                rv = quote! { #rv | #alias };
+                seen_names.insert(folded.clone(), (seen_index, key.clone()));
+                patterns.push(folded);
             }
+
+            let duplicate_check = if check_duplicates {
+                Some(quote! {
+                    if self.seen[#seen_index] {
+                        return #krate::__derive::Err(#krate::__derive::new_duplicate_field_error(#name));
+                    }
+                    self.seen[#seen_index] = true;
+                })
+            } else {
+                None
+            };
+            let deserialize_into = match x.with(&krate) {
+                Some(with) => quote!(#with::deserialize_into),
+                None => quote!(#krate::Deserialize::deserialize_into),
+            };
             Some(quote! {
-                #rv => return ::deser::__derive::Ok(::deser::__derive::Some(::deser::Deserialize::deserialize_into(&mut self.#fieldname))),
+                #(#patterns)|* => {
+                    #duplicate_check
+                    return #krate::__derive::Ok(#krate::__derive::Some(#deserialize_into(&mut self.#fieldname)));
+                }
             })
         })
         .collect::<Vec<_>>();
 
-    if let Some((first_duplicate_name, field)) = first_duplicate_name {
+    if let Some((first_duplicate_name, other, field)) = first_duplicate_name {
         return Err(syn::Error::new_spanned(
             field,
-            format!("field name '{}' used more than once", first_duplicate_name),
+            if match_keys == MatchKeys::Exact {
+                format!("field name '{}' used more than once", first_duplicate_name)
+            } else {
+                format!(
+                    "field name '{}' is ambiguous with '{}' with match_keys = \"{}\"",
+                    first_duplicate_name,
+                    other,
+                    match_keys.as_str()
+                )
+            },
         ));
     }
 
+    let match_key = match match_keys {
+        MatchKeys::Exact => quote!(__key),
+        MatchKeys::CaseInsensitive => quote!(&*#krate::__derive::case_fold_key(__key)),
+        MatchKeys::Normalized => quote!(&*#krate::__derive::normalize_key(__key)),
+    };
+
+    let expected_fields = attrs
+        .iter()
+        .filter(|x| !x.flatten())
+        .map(|x| x.name(&container_attrs, Direction::Deserialize))
+        .collect::<Vec<_>>();
+    let unknown_field = if container_attrs.deny_unknown_fields() {
+        quote! {
+            return #krate::__derive::Err(#krate::__derive::new_unknown_field_error(
+                &__key,
+                &[#(#expected_fields),*],
+            ))
+        }
+    } else {
+        quote! { #krate::de::SinkHandle::null() }
+    };
+
+    let (seen_field, seen_init) = if check_duplicates {
+        (
+            Some(quote! { seen: [bool; #seen_count], }),
+            Some(quote! { seen: [false; #seen_count], }),
+        )
+    } else {
+        (None, None)
+    };
+
     let wrapper_generics = with_lifetime_bound(&input.generics, "'__a");
     let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
-    let bound = syn::parse_quote!(::deser::Serialize);
-    let bounded_where_clause = where_clause_with_bound(&input.generics, bound);
+    let bounded_where_clause = where_clause_with_custom_bound(
+        &input.generics,
+        container_attrs.bound(Direction::Deserialize),
+        quote!(#krate::Deserialize),
+    );
 
     let field_stage1_default = attrs
         .iter()
         .map(|attrs| match attrs.default() {
             Some(TypeDefault::Implicit) => {
-                quote! { take().unwrap_or_else(::deser::__derive::Default::default) }
+                quote! { take().unwrap_or_else(#krate::__derive::Default::default) }
             }
             Some(TypeDefault::Explicit(path)) => {
                 quote! { take().unwrap_or_else(#path) }
             }
-            None => quote!(take()),
+            None if attrs.flatten() || attrs.with(&krate).is_some() => quote!(take()),
+            None => {
+                let ty = &attrs.field().ty;
+                quote! { take().or_else(<#ty as #krate::de::Deserialize>::missing_value) }
+            }
         })
         .collect::<Vec<_>>();
     let field_take = sink_fieldname
@@ -147,22 +222,25 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
                 // this should never happen unless the inner deserializer fucked up
                 let error = format!(
                     "Failed to deserialize flattened field '{}'",
-                    attrs.name(&container_attrs)
+                    attrs.name(&container_attrs, Direction::Deserialize)
                 );
                 quote! {
                     match #name {
-                        ::deser::__derive::Some(val) => val,
-                        ::deser::__derive::None => return ::deser::__derive::Err(::deser::Error::new(::deser::ErrorKind::Unexpected, #error))
+                        #krate::__derive::Some(val) => val,
+                        #krate::__derive::None => return #krate::__derive::Err(#krate::Error::new(#krate::ErrorKind::Unexpected, #error))
                     }
                 }
             } else if container_attrs.default().is_some() {
                 quote! { #name.unwrap() }
             } else {
-                let str_name = attrs.name(&container_attrs);
+                let str_name = attrs.name(&container_attrs, Direction::Deserialize);
                 quote! {
                     match #name {
-                        ::deser::__derive::Some(val) => val,
-                        ::deser::__derive::None => return ::deser::__derive::Err(::deser::__derive::new_missing_field_error(#str_name))
+                        #krate::__derive::Some(val) => val,
+                        #krate::__derive::None => return #krate::__derive::Err(#krate::__derive::new_missing_field_error(
+                            #str_name,
+                            &[#(#expected_fields),*],
+                        ))
                     }
                 }
             }
@@ -181,6 +259,84 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
             },
         )
         .collect::<Vec<_>>();
+    // flattened maps claim every key, so they are tried after all other
+    // flattened fields regardless of declaration order.
+    let mut flatten_order = attrs
+        .iter()
+        .zip(sink_fieldname.iter())
+        .filter(|(attrs, _)| attrs.flatten())
+        .collect::<Vec<_>>();
+    flatten_order.sort_by_key(|(attrs, _)| is_map_type(&attrs.field().ty));
+    let flatten_matcher = flatten_order
+        .into_iter()
+        .map(|(attrs, name)| {
+            let forward = |key: TokenStream| {
+                quote! {
+                    if let #krate::__derive::Some(__sink) = self.#name.borrow_mut().value_for_key(#key, __state)? {
+                        return #krate::__derive::Ok(#krate::__derive::Some(__sink));
+                    }
+                }
+            };
+            match attrs.flatten_prefix() {
+                Some(prefix) => {
+                    let forward = forward(quote!(__stripped));
+                    quote! {
+                        if let #krate::__derive::Some(__stripped) = __key.strip_prefix(#prefix) {
+                            #forward
+                        }
+                    }
+                }
+                None => forward(quote!(__key)),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut validators = Vec::new();
+    for (attrs, fieldname) in attrs.iter().zip(fieldname.iter()) {
+        let name = attrs.name(&container_attrs, Direction::Deserialize);
+        for validator in attrs.validators() {
+            let check = match validator {
+                Validator::Custom(path) => quote! { #path(&__value.#fieldname) },
+                Validator::Range(min, max) => {
+                    // bounds are cast so that integer literals work for float fields
+                    let ty = &attrs.field().ty;
+                    let min = option_tokens(&krate, &min.as_ref().map(|x| quote!(#x as #ty)));
+                    let max = option_tokens(&krate, &max.as_ref().map(|x| quote!(#x as #ty)));
+                    quote! { #krate::__derive::validate_range(&__value.#fieldname, #min, #max) }
+                }
+                Validator::Length(min, max) => {
+                    let min = option_tokens(&krate, min);
+                    let max = option_tokens(&krate, max);
+                    quote! { #krate::__derive::validate_length(__value.#fieldname.len(), #min, #max) }
+                }
+                Validator::NonEmpty => quote! {
+                    #krate::__derive::validate_non_empty(__value.#fieldname.is_empty())
+                },
+            };
+            validators.push(quote! {
+                #krate::__derive::collect_field_error(&mut __errors, #name, #check);
+            });
+        }
+    }
+    if let Some(path) = container_attrs.validate() {
+        validators.push(quote! {
+            #krate::__derive::collect_error(&mut __errors, #path(&__value));
+        });
+    }
+    let validation = if validators.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            let mut __errors = #krate::__derive::Vec::new();
+            #(#validators)*
+            #krate::__derive::finish_validation(__errors)?;
+        })
+    };
+
+    let (target, target_ctor) = match container_attrs.remote() {
+        Some(remote) => (quote!(#remote), quote!(#remote)),
+        None => (quote!(#ident #ty_generics), quote!(#ident)),
+    };
 
     let stage2_default = if container_attrs.default().is_some() {
         let need_container_default = sink_fieldname
@@ -200,7 +356,7 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
                 need_container_default.into_iter().unzip();
             let type_default = match container_attrs.default().unwrap() {
                 TypeDefault::Implicit => quote! {
-                    <#ident as ::deser::__derive::Default>::default()
+                    <#target as #krate::__derive::Default>::default()
                 },
                 TypeDefault::Explicit(path) => quote! { #path() },
             };
@@ -209,10 +365,10 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
                     #(
                         #sink_name.as_ref().is_none()
                     ),*
-                ].iter().any(|x| *x) {
+                ].contains(&true) {
                     let __default = #type_default;
                     #(
-                        #sink_name = #sink_name.or_else(|| Some(__default.#original_name));
+                        #sink_name = #sink_name.or_else(|| #krate::__derive::Some(__default.#original_name));
                     )*
                 }
             })
@@ -223,79 +379,107 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
         None
     };
 
+    let sink_init = quote! {
+        #krate::de::SinkHandle::boxed(__Sink {
+            slot: __slot,
+            key: #krate::__derive::None,
+            #seen_init
+            #(
+                #sink_fieldname: #sink_defaults,
+            )*
+        })
+    };
+    let deserialize_impl = if let Some(remote) = container_attrs.remote() {
+        let vis = &input.vis;
+        quote! {
+            // converting the mirror ensures it declares the remote's fields with
+            // the same types and keeps its fields from being reported as unused.
+            #[allow(dead_code)]
+            fn __check_remote_fields(__mirror: #ident) -> #remote {
+                #remote { #(#fieldname: __mirror.#fieldname),* }
+            }
+
+            impl #ident {
+                #[allow(dead_code)]
+                #vis fn deserialize_into(
+                    __slot: &mut #krate::__derive::Option<#remote>,
+                ) -> #krate::de::SinkHandle {
+                    #sink_init
+                }
+            }
+        }
+    } else {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #krate::Deserialize for #ident #ty_generics #bounded_where_clause {
+                fn deserialize_into(
+                    __slot: &mut #krate::__derive::Option<Self>,
+                ) -> #krate::de::SinkHandle {
+                    #sink_init
+                }
+            }
+        }
+    };
+
     Ok(quote! {
         #[allow(non_upper_case_globals)]
         const #dummy: () = {
             struct __Sink #wrapper_impl_generics #where_clause {
-                slot: &'__a mut ::deser::__derive::Option<#ident #ty_generics>,
-                key: ::deser::__derive::Option<String>,
+                slot: &'__a mut #krate::__derive::Option<#target>,
+                key: #krate::__derive::Option<#krate::__derive::String>,
+                #seen_field
                 #(
                     #sink_fieldname: #sink_fieldty,
                 )*
             }
 
-            #[automatically_derived]
-            impl #impl_generics ::deser::Deserialize for #ident #ty_generics #bounded_where_clause {
-                fn deserialize_into(
-                    __slot: &mut ::deser::__derive::Option<Self>,
-                ) -> ::deser::de::SinkHandle {
-                    ::deser::de::SinkHandle::boxed(__Sink {
-                        slot: __slot,
-                        key: ::deser::__derive::None,
-                        #(
-                            #sink_fieldname: #sink_defaults,
-                        )*
-                    })
-                }
-            }
+            #deserialize_impl
 
             #[automatically_derived]
-            impl #wrapper_impl_generics ::deser::de::Sink for __Sink #wrapper_ty_generics #bounded_where_clause {
-                fn descriptor(&self) -> &dyn ::deser::Descriptor {
+            impl #wrapper_impl_generics #krate::de::Sink for __Sink #wrapper_ty_generics #bounded_where_clause {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
                     &__Descriptor
                 }
 
-                fn map(&mut self, __state: &::deser::de::DeserializerState)
-                    -> ::deser::__derive::Result<()>
+                fn map(&mut self, __state: &#krate::de::DeserializerState)
+                    -> #krate::__derive::Result<()>
                 {
-                    ::deser::__derive::Ok(())
+                    #krate::__derive::Ok(())
                 }
 
-                fn next_key(&mut self, __state: &::deser::de::DeserializerState)
-                    -> ::deser::__derive::Result<::deser::de::SinkHandle>
+                fn next_key(&mut self, __state: &#krate::de::DeserializerState)
+                    -> #krate::__derive::Result<#krate::de::SinkHandle>
                 {
-                    ::deser::__derive::Ok(::deser::de::Deserialize::deserialize_into(&mut self.key))
+                    #krate::__derive::Ok(#krate::de::Deserialize::deserialize_into(&mut self.key))
                 }
 
-                fn next_value(&mut self, __state: &::deser::de::DeserializerState)
-                    -> ::deser::__derive::Result<::deser::de::SinkHandle>
+                fn next_value(&mut self, __state: &#krate::de::DeserializerState)
+                    -> #krate::__derive::Result<#krate::de::SinkHandle>
                 {
                     let __key = self.key.take().unwrap();
-                    ::deser::__derive::Ok(match self.value_for_key(&__key, __state)? {
-                        ::deser::__derive::Some(__sink) => __sink,
-                        ::deser::__derive::None => ::deser::de::SinkHandle::null(),
+                    #krate::__derive::Ok(match self.value_for_key(&__key, __state)? {
+                        #krate::__derive::Some(__sink) => __sink,
+                        #krate::__derive::None => #unknown_field,
                     })
                 }
 
-                fn value_for_key(&mut self, __key: &str, __state: &::deser::de::DeserializerState)
-                    -> ::deser::__derive::Result<::deser::__derive::Option<::deser::de::SinkHandle>>
+                fn value_for_key(&mut self, __key: &str, __state: &#krate::de::DeserializerState)
+                    -> #krate::__derive::Result<#krate::__derive::Option<#krate::de::SinkHandle>>
                 {
-                    match __key {
+                    match #match_key {
                         #(
                             #matcher
                         )*
-                        __other => {
+                        _ => {
                             #(
-                                if let ::deser::__derive::Some(__sink) = self.#flatten_fields.borrow_mut().value_for_key(__other, __state)? {
-                                    return ::deser::__derive::Ok(::deser::__derive::Some(__sink));
-                                }
+                                #flatten_matcher
                             )*
                         }
                     }
-                    ::deser::__derive::Ok(::deser::__derive::None)
+                    #krate::__derive::Ok(#krate::__derive::None)
                 }
 
-                fn finish(&mut self, __state: &::deser::de::DeserializerState) -> ::deser::__derive::Result<()> {
+                fn finish(&mut self, __state: &#krate::de::DeserializerState) -> #krate::__derive::Result<()> {
                     #![allow(unused_mut)]
                     #(
                         self.#flatten_fields.borrow_mut().finish(__state)?;
@@ -304,20 +488,158 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
                         let mut #sink_fieldname = self.#sink_fieldname.#field_stage1_default;
                     )*
                     #stage2_default
-                    *self.slot = ::deser::__derive::Some(#ident {
+                    let __value = #target_ctor {
                         #(
                             #fieldname: #field_take,
                         )*
-                    });
-                    ::deser::__derive::Ok(())
+                    };
+                    #validation
+                    *self.slot = #krate::__derive::Some(__value);
+                    #krate::__derive::Ok(())
                 }
             }
 
             struct __Descriptor;
 
-            impl ::deser::Descriptor for __Descriptor {
-                fn name(&self) -> ::deser::__derive::Option<&::deser::__derive::str> {
-                    ::deser::__derive::Some(#type_name)
+            impl #krate::Descriptor for __Descriptor {
+                fn name(&self) -> #krate::__derive::Option<&#krate::__derive::str> {
+                    #krate::__derive::Some(#type_name)
+                }
+
+                fn kind(&self) -> #krate::Kind {
+                    #krate::Kind::Map
+                }
+            }
+        };
+    })
+}
+
+fn derive_as_string(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let dummy = syn::Ident::new(
+        &format!("_DESER_DESERIALIZE_IMPL_FOR_{}", ident),
+        Span::call_site(),
+    );
+    let container_attrs = ContainerAttrs::of(input)?;
+    let krate = container_attrs.crate_path();
+    let type_name = container_attrs.container_name(Direction::Deserialize);
+    let where_clause = where_clause_with_predicates(
+        &input.generics,
+        container_attrs.bound(Direction::Deserialize),
+        vec![
+            syn::parse_quote!(Self: #krate::__derive::FromStr),
+            syn::parse_quote!(
+                <Self as #krate::__derive::FromStr>::Err: #krate::__derive::StdError
+                    + #krate::__derive::Send
+                    + #krate::__derive::Sync
+                    + 'static
+            ),
+        ],
+    );
+
+    Ok(quote! {
+        #[allow(non_upper_case_globals)]
+        const #dummy: () = {
+            #[automatically_derived]
+            impl #impl_generics #krate::Deserialize for #ident #ty_generics #where_clause {
+                fn deserialize_into(
+                    __slot: &mut #krate::__derive::Option<Self>,
+                ) -> #krate::de::SinkHandle {
+                    #krate::__derive::as_string::deserialize_into_named(__slot, #type_name)
+                }
+            }
+        };
+    })
+}
+
+fn derive_seq_struct(
+    input: &syn::DeriveInput,
+    fields: &syn::FieldsNamed,
+    container_attrs: &ContainerAttrs,
+) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let dummy = syn::Ident::new(
+        &format!("_DESER_DESERIALIZE_IMPL_FOR_{}", ident),
+        Span::call_site(),
+    );
+    let krate = container_attrs.crate_path();
+    let type_name = container_attrs.container_name(Direction::Deserialize);
+
+    for field in &fields.named {
+        ensure_no_field_attrs(field)?;
+    }
+    let fieldname = fields.named.iter().map(|x| &x.ident).collect::<Vec<_>>();
+    let fieldty = fields.named.iter().map(|x| &x.ty).collect::<Vec<_>>();
+
+    let wrapper_generics = with_lifetime_bound(&input.generics, "'__a");
+    let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
+    let bounded_where_clause = where_clause_with_custom_bound(
+        &input.generics,
+        container_attrs.bound(Direction::Deserialize),
+        quote!(#krate::Deserialize),
+    );
+
+    // the sequence handling is delegated to the sink of the matching tuple
+    Ok(quote! {
+        #[allow(non_upper_case_globals)]
+        const #dummy: () = {
+            struct __Sink #wrapper_impl_generics #where_clause {
+                slot: &'__a mut #krate::__derive::Option<#ident #ty_generics>,
+                sink: #krate::de::OwnedSink<(#(#fieldty,)*)>,
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #krate::Deserialize for #ident #ty_generics #bounded_where_clause {
+                fn deserialize_into(
+                    __slot: &mut #krate::__derive::Option<Self>,
+                ) -> #krate::de::SinkHandle {
+                    #krate::de::SinkHandle::boxed(__Sink {
+                        slot: __slot,
+                        sink: #krate::de::OwnedSink::deserialize(),
+                    })
+                }
+            }
+
+            #[automatically_derived]
+            impl #wrapper_impl_generics #krate::de::Sink for __Sink #wrapper_ty_generics #bounded_where_clause {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
+                    &__Descriptor
+                }
+
+                fn seq(&mut self, __state: &#krate::de::DeserializerState)
+                    -> #krate::__derive::Result<()>
+                {
+                    self.sink.borrow_mut().seq(__state)
+                }
+
+                fn next_value(&mut self, __state: &#krate::de::DeserializerState)
+                    -> #krate::__derive::Result<#krate::de::SinkHandle>
+                {
+                    self.sink.borrow_mut().next_value(__state)
+                }
+
+                fn finish(&mut self, __state: &#krate::de::DeserializerState) -> #krate::__derive::Result<()> {
+                    self.sink.borrow_mut().finish(__state)?;
+                    if let #krate::__derive::Some((#(#fieldname,)*)) = self.sink.take() {
+                        *self.slot = #krate::__derive::Some(#ident {
+                            #(#fieldname,)*
+                        });
+                    }
+                    #krate::__derive::Ok(())
+                }
+            }
+
+            struct __Descriptor;
+
+            impl #krate::Descriptor for __Descriptor {
+                fn name(&self) -> #krate::__derive::Option<&#krate::__derive::str> {
+                    #krate::__derive::Some(#type_name)
+                }
+
+                fn kind(&self) -> #krate::Kind {
+                    #krate::Kind::Seq
                 }
             }
         };
@@ -342,29 +664,26 @@ pub fn derive_enum(
     );
 
     let container_attrs = ContainerAttrs::of(input)?;
-    let var_idents = enumeration
-        .variants
-        .iter()
-        .map(|variant| match variant.fields {
-            syn::Fields::Unit => Ok(&variant.ident),
-            _ => Err(syn::Error::new_spanned(
-                variant,
-                "Invalid variant: only simple enum variants without fields are supported",
-            )),
-        })
-        .collect::<syn::Result<Vec<_>>>()?;
+    let krate = container_attrs.crate_path();
     let attrs = enumeration
         .variants
         .iter()
         .map(EnumVariantAttrs::of)
         .collect::<syn::Result<Vec<_>>>()?;
+    ensure_valid_other_variant(&container_attrs, &attrs)?;
+    let var_idents = attrs
+        .iter()
+        .filter(|x| !x.captures_other())
+        .map(|x| &x.variant().ident)
+        .collect::<Vec<_>>();
 
     let mut seen_names = HashSet::new();
     let mut first_duplicate_name = None;
     let matcher = attrs
         .iter()
+        .filter(|x| !x.captures_other())
         .map(|x| {
-            let name = x.name(&container_attrs).to_string();
+            let name = x.name(&container_attrs, Direction::Deserialize).to_string();
             if first_duplicate_name.is_none() && seen_names.contains(&name) {
                 first_duplicate_name = Some((name.clone(), x.variant()));
             }
@@ -396,47 +715,141 @@ pub fn derive_enum(
         ));
     }
 
+    let fallback = match attrs.iter().find(|x| x.other()) {
+        Some(x) if x.captures_other() => {
+            let var_ident = &x.variant().ident;
+            quote! {
+                __other => #ident::#var_ident(#krate::__derive::Into::into(
+                    #krate::__derive::String::from(__other)
+                )),
+            }
+        }
+        Some(x) => {
+            let var_ident = &x.variant().ident;
+            quote! {
+                _ => #ident::#var_ident,
+            }
+        }
+        None => {
+            let expected = attrs
+                .iter()
+                .map(|x| x.name(&container_attrs, Direction::Deserialize))
+                .collect::<Vec<_>>();
+            quote! {
+                __other => return #krate::__derive::Err(#krate::__derive::new_unknown_variant_error(
+                    __other,
+                    &[#(#expected),*],
+                ))
+            }
+        }
+    };
+
+    let repr = container_attrs.repr();
+    let type_name = container_attrs.container_name(Direction::Deserialize);
+    let kind = match (repr.accepts_name(), repr.accepts_int()) {
+        (true, true) => quote!(#krate::Kind::Any),
+        (true, false) => quote!(#krate::Kind::String),
+        (false, _) => quote!(#krate::Kind::Int),
+    };
+    let name_handler = if repr.accepts_name() {
+        Some(quote! {
+            #krate::Atom::Str(ref __s) => match &**__s {
+                #( #matcher => #ident::#var_idents, )*
+                #fallback
+            },
+        })
+    } else {
+        None
+    };
+    let int_handler = if repr.accepts_int() {
+        let int_fallback = match attrs.iter().find(|x| x.other()) {
+            Some(x) => {
+                let var_ident = &x.variant().ident;
+                quote! { _ => #ident::#var_ident, }
+            }
+            None => quote! {
+                _ => return #krate::__derive::Err(
+                    #krate::Error::new(#krate::ErrorKind::Unexpected, "unexpected value for enum")
+                )
+            },
+        };
+        let discriminants = var_idents
+            .iter()
+            .map(|var_ident| {
+                syn::Ident::new(&format!("__DISCRIMINANT_{}", var_ident), Span::call_site())
+            })
+            .collect::<Vec<_>>();
+        quote! {
+            #(
+                const #discriminants: i128 = #ident::#var_idents as i128;
+            )*
+            match #krate::__derive::discriminant_from_atom(&__other) {
+                #krate::__derive::Some(__value) => match __value {
+                    #( #discriminants => #ident::#var_idents, )*
+                    #int_fallback
+                },
+                #krate::__derive::None => return self.unexpected_atom(__other, __state),
+            }
+        }
+    } else {
+        quote! {
+            return self.unexpected_atom(__other, __state)
+        }
+    };
+
     Ok(quote! {
         #[allow(non_upper_case_globals)]
         const #dummy: () = {
             #[repr(transparent)]
             struct __SlotWrapper {
-                slot: ::deser::__derive::Option<#ident>,
+                slot: #krate::__derive::Option<#ident>,
             }
 
             #[automatically_derived]
-            impl ::deser::de::Deserialize for #ident {
+            impl #krate::de::Deserialize for #ident {
                 fn deserialize_into(
-                    __slot: &mut ::deser::__derive::Option<Self>
-                ) -> ::deser::de::SinkHandle {
-                    ::deser::de::SinkHandle::to(unsafe {
+                    __slot: &mut #krate::__derive::Option<Self>
+                ) -> #krate::de::SinkHandle {
+                    #krate::de::SinkHandle::to(unsafe {
                         &mut *{
                             __slot
-                            as *mut ::deser::__derive::Option<Self>
+                            as *mut #krate::__derive::Option<Self>
                             as *mut __SlotWrapper
                         }
                     })
                 }
             }
 
-            impl ::deser::de::Sink for __SlotWrapper {
+            impl #krate::de::Sink for __SlotWrapper {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
+                    &__Descriptor
+                }
+
                 fn atom(
                     &mut self,
-                    __atom: ::deser::Atom,
-                    __state: &::deser::de::DeserializerState
-                ) -> ::deser::__derive::Result<()> {
-                    let s = match __atom {
-                        ::deser::Atom::Str(ref s) => &s as &::deser::__derive::str,
-                        __other => return self.unexpected_atom(__other, __state),
-                    };
-                    let value = match s {
-                        #( #matcher => #ident::#var_idents, )*
-                        _ => return ::deser::__derive::Err(
-                            ::deser::Error::new(::deser::ErrorKind::Unexpected, "unexpected value for enum")
-                        )
+                    __atom: #krate::Atom,
+                    __state: &#krate::de::DeserializerState
+                ) -> #krate::__derive::Result<()> {
+                    let value = match __atom {
+                        #name_handler
+                        __other => {
+                            #int_handler
+                        }
                     };
-                    self.slot = ::deser::__derive::Some(value);
-                    ::deser::__derive::Ok(())
+                    self.slot = #krate::__derive::Some(value);
+                    #krate::__derive::Ok(())
+                }
+            }
+
+            struct __Descriptor;
+
+            impl #krate::Descriptor for __Descriptor {
+                fn name(&self) -> #krate::__derive::Option<&#krate::__derive::str> {
+                    #krate::__derive::Some(#type_name)
+                }
+
+                fn kind(&self) -> #krate::Kind {
+                    #kind
                 }
             }
         };
@@ -454,7 +867,8 @@ fn derive_newtype_struct(input: &syn::DeriveInput, field: &syn::Field) -> syn::R
     // TODO: we want to report the type name here but the current descriptor
     // interface does not let us.  https://github.com/mitsuhiko/deser/issues/8
     let container_attrs = ContainerAttrs::of(input)?;
-    let _type_name = container_attrs.container_name();
+    let krate = container_attrs.crate_path();
+    let _type_name = container_attrs.container_name(Direction::Deserialize);
 
     ensure_no_field_attrs(field)?;
 
@@ -462,52 +876,59 @@ fn derive_newtype_struct(input: &syn::DeriveInput, field: &syn::Field) -> syn::R
 
     let wrapper_generics = with_lifetime_bound(&input.generics, "'__a");
     let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
-    let bound = syn::parse_quote!(::deser::Serialize);
-    let bounded_where_clause = where_clause_with_bound(&input.generics, bound);
+    let bounded_where_clause = where_clause_with_custom_bound(
+        &input.generics,
+        container_attrs.bound(Direction::Deserialize),
+        quote!(#krate::Deserialize),
+    );
 
     Ok(quote! {
         #[allow(non_upper_case_globals)]
         const #dummy: () = {
             struct __Sink #wrapper_impl_generics #where_clause {
-                slot: &'__a mut ::deser::__derive::Option<#ident #ty_generics>,
-                sink: ::deser::de::OwnedSink<#field_type #ty_generics>,
+                slot: &'__a mut #krate::__derive::Option<#ident #ty_generics>,
+                sink: #krate::de::OwnedSink<#field_type #ty_generics>,
             }
 
             #[automatically_derived]
-            impl #impl_generics ::deser::de::Deserialize for #ident #ty_generics #bounded_where_clause {
+            impl #impl_generics #krate::de::Deserialize for #ident #ty_generics #bounded_where_clause {
                 fn deserialize_into(
-                    __slot: &mut ::deser::__derive::Option<Self>
-                ) -> ::deser::de::SinkHandle {
-                    ::deser::de::SinkHandle::boxed(__Sink {
+                    __slot: &mut #krate::__derive::Option<Self>
+                ) -> #krate::de::SinkHandle {
+                    #krate::de::SinkHandle::boxed(__Sink {
                         slot: __slot,
-                        sink: ::deser::de::OwnedSink::deserialize(),
+                        sink: #krate::de::OwnedSink::deserialize(),
                     })
                 }
+
+                fn missing_value() -> #krate::__derive::Option<Self> {
+                    <#field_type as #krate::de::Deserialize>::missing_value().map(#ident)
+                }
             }
 
-            impl #wrapper_impl_generics ::deser::de::Sink for __Sink #wrapper_ty_generics #bounded_where_clause {
-                fn atom(&mut self, __atom: ::deser::Atom, __state: &::deser::de::DeserializerState)
-                    -> ::deser::__derive::Result<()>
+            impl #wrapper_impl_generics #krate::de::Sink for __Sink #wrapper_ty_generics #bounded_where_clause {
+                fn atom(&mut self, __atom: #krate::Atom, __state: &#krate::de::DeserializerState)
+                    -> #krate::__derive::Result<()>
                 {
                     self.sink.borrow_mut().atom(__atom, __state)
                 }
 
-                fn map(&mut self, __state: &::deser::de::DeserializerState) -> ::deser::__derive::Result<()> {
+                fn map(&mut self, __state: &#krate::de::DeserializerState) -> #krate::__derive::Result<()> {
                     self.sink.borrow_mut().map(__state)
                 }
 
-                fn seq(&mut self, __state: &::deser::de::DeserializerState) -> ::deser::__derive::Result<()>  {
+                fn seq(&mut self, __state: &#krate::de::DeserializerState) -> #krate::__derive::Result<()>  {
                     self.sink.borrow_mut().seq(__state)
                 }
 
-                fn next_key(&mut self, __state: &::deser::de::DeserializerState)
-                    -> ::deser::__derive::Result<::deser::de::SinkHandle>
+                fn next_key(&mut self, __state: &#krate::de::DeserializerState)
+                    -> #krate::__derive::Result<#krate::de::SinkHandle>
                 {
                     self.sink.borrow_mut().next_key(__state)
                 }
 
-                fn next_value(&mut self, __state: &::deser::de::DeserializerState)
-                    -> ::deser::__derive::Result<::deser::de::SinkHandle>
+                fn next_value(&mut self, __state: &#krate::de::DeserializerState)
+                    -> #krate::__derive::Result<#krate::de::SinkHandle>
                 {
                     self.sink.borrow_mut().next_value(__state)
                 }
@@ -515,25 +936,54 @@ fn derive_newtype_struct(input: &syn::DeriveInput, field: &syn::Field) -> syn::R
                 fn value_for_key(
                     &mut self,
                     __key: &str,
-                    __state: &::deser::de::DeserializerState,
-                ) -> ::deser::__derive::Result<::deser::__derive::Option<::deser::de::SinkHandle>> {
+                    __state: &#krate::de::DeserializerState,
+                ) -> #krate::__derive::Result<#krate::__derive::Option<#krate::de::SinkHandle>> {
                     self.sink.borrow_mut().value_for_key(__key, __state)
                 }
 
-                fn finish(&mut self, __state: &::deser::de::DeserializerState) -> ::deser::__derive::Result<()> {
+                fn finish(&mut self, __state: &#krate::de::DeserializerState) -> #krate::__derive::Result<()> {
                     self.sink.borrow_mut().finish(__state)?;
                     *self.slot = self.sink.take().map(#ident);
-                    Ok(())
+                    #krate::__derive::Ok(())
                 }
 
-                fn descriptor(&self) -> &dyn ::deser::Descriptor {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
                     self.sink.borrow().descriptor()
                 }
 
-                fn expecting(&self) -> ::deser::__derive::StrCow<'_> {
+                fn expecting(&self) -> #krate::__derive::StrCow<'_> {
                     self.sink.borrow().expecting()
                 }
             }
         };
     })
 }
+
+/// Checks if a type is (an optional) `HashMap` or `BTreeMap`.
+fn is_map_type(ty: &syn::Type) -> bool {
+    let segment = match ty {
+        syn::Type::Path(path) => match path.path.segments.last() {
+            Some(segment) => segment,
+            None => return false,
+        },
+        _ => return false,
+    };
+    match segment.ident.to_string().as_str() {
+        "HashMap" | "BTreeMap" => true,
+        "Option" => match segment.arguments {
+            syn::PathArguments::AngleBracketed(ref args) => match args.args.first() {
+                Some(syn::GenericArgument::Type(ty)) => is_map_type(ty),
+                _ => false,
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn option_tokens<T: quote::ToTokens>(krate: &syn::Path, value: &Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote!(#krate::__derive::Some(#value)),
+        None => quote!(#krate::__derive::None),
+    }
+}
@@ -1,10 +1,18 @@
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 
-use crate::attr::{ensure_no_field_attrs, ContainerAttrs, EnumVariantAttrs, FieldAttrs};
-use crate::bound::{where_clause_with_bound, with_lifetime_bound};
+use crate::attr::{
+    ensure_no_field_attrs, ensure_valid_other_variant, ContainerAttrs, Direction, EnumVariantAttrs,
+    FieldAttrs,
+};
+use crate::bound::{
+    where_clause_with_custom_bound, where_clause_with_predicates, with_lifetime_bound,
+};
 
 pub fn derive_serialize(input: &mut syn::DeriveInput) -> syn::Result<TokenStream> {
+    if ContainerAttrs::of(input)?.as_string() {
+        return derive_as_string(input);
+    }
     match &input.data {
         syn::Data::Struct(syn::DataStruct {
             fields: syn::Fields::Named(fields),
@@ -28,7 +36,11 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
     );
 
     let container_attrs = ContainerAttrs::of(input)?;
-    let type_name = container_attrs.container_name();
+    if container_attrs.as_seq() {
+        return derive_seq_struct(input, fields, &container_attrs);
+    }
+    let krate = container_attrs.crate_path();
+    let type_name = container_attrs.container_name(Direction::Serialize);
     let attrs = fields
         .named
         .iter()
@@ -37,7 +49,7 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
 
     let temp_emitter = if attrs.iter().any(|x| x.flatten()) {
         Some(quote! {
-            nested_emitter: ::deser::__derive::Option<::deser::__derive::Box<dyn ::deser::ser::StructEmitter + '__a>>,
+            nested_emitter: #krate::__derive::Option<#krate::__derive::Box<dyn #krate::ser::StructEmitter + '__a>>,
             nested_emitter_exhausted: bool,
         })
     } else {
@@ -45,7 +57,7 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
     };
     let temp_emitter_init = if attrs.iter().any(|x| x.flatten()) {
         Some(quote! {
-            nested_emitter: ::deser::__derive::None,
+            nested_emitter: #krate::__derive::None,
             nested_emitter_exhausted: true,
         })
     } else {
@@ -66,7 +78,11 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
                 quote! {}
             };
             if !attrs.flatten() {
-                let fieldstr = attrs.name(&container_attrs);
+                let fieldstr = attrs.name(&container_attrs, Direction::Serialize);
+                let to_handle = match attrs.with(&krate) {
+                    Some(with) => quote!(#with::serialize_handle(&self.data.#name)),
+                    None => quote!(#krate::ser::SerializeHandle::to(&self.data.#name)),
+                };
                 let field_skip = if let Some(path) = attrs.skip_serializing_if() {
                     quote! {
                         if #path(&self.data.#name) {
@@ -76,14 +92,16 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
                 } else {
                     quote! {}
                 };
+                let group_skip = group_skip(&krate, attrs.groups(), quote!());
                 quote! {
                     #index => {
                         self.index = __index + 1;
+                        #group_skip
                         #field_skip
-                        let __handle = ::deser::ser::SerializeHandle::to(&self.data.#name);
+                        let __handle = #to_handle;
                         #optional_skip
-                        return ::deser::__derive::Ok(::deser::__derive::Some((
-                            ::deser::__derive::Cow::Borrowed(#fieldstr),
+                        return #krate::__derive::Ok(#krate::__derive::Some((
+                            #krate::__derive::Cow::Borrowed(#fieldstr),
                             __handle,
                         )));
                     }
@@ -99,44 +117,62 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
                 } else {
                     quote! {}
                 };
+                let group_skip = group_skip(&krate, attrs.groups(), quote!(self.index += 1;));
+                let key = match attrs.flatten_prefix() {
+                    Some(prefix) => quote! {
+                        #krate::__derive::StrCow::Owned(#krate::__derive::format!("{}{}", #prefix, __key))
+                    },
+                    None => quote!(__key),
+                };
                 quote! {
                     #index => {
+                        #group_skip
                         #field_skip
                         if self.nested_emitter_exhausted {
-                            self.nested_emitter = match ::deser::ser::Serialize::serialize(&self.data.#name, __state)? {
-                                ::deser::ser::Chunk::Struct(__inner) => {
-                                    Some(__inner)
+                            self.nested_emitter = match #krate::ser::Serialize::serialize(&self.data.#name, __state)? {
+                                #krate::ser::Chunk::Struct(__inner) => {
+                                    #krate::__derive::Some(__inner)
+                                }
+                                #krate::ser::Chunk::Atom(#krate::Atom::Null) => {
+                                    self.index += 1;
+                                    #krate::ser::Serialize::finish(&self.data.#name, __state)?;
+                                    continue;
                                 }
-                                _ => return ::deser::__derive::Err(::deser::Error::new(
-                                    ::deser::ErrorKind::Unexpected,
-                                    "unable to flatten on struct into struct"
+                                #krate::ser::Chunk::Map(__inner) => {
+                                    #krate::__derive::Some(#krate::__derive::map_as_struct_emitter(__inner))
+                                }
+                                _ => return #krate::__derive::Err(#krate::Error::new(
+                                    #krate::ErrorKind::Unexpected,
+                                    "can only flatten structs and maps into struct"
                                 ))
                             };
                             self.nested_emitter_exhausted = false;
                         }
                         match self.nested_emitter.as_mut().unwrap().next(__state)? {
-                            ::deser::__derive::None => {
-                                self.index += 1;
-                                self.nested_emitter_exhausted = true;
-                                ::deser::ser::Serialize::finish(&self.data.#name, __state)?;
-                                continue;
-                            }
+                            #krate::__derive::None => {}
                             // we need this transmute here because of limitations in the borrow
                             // checker.  The borrow checker does not understand that the borrow
                             // does not continue into the next loop iteration.  If polonius ever
                             // makes it into Rust this can go.
                             //
                             // This can be validated with `-Zpolonius`
-                            ::deser::__derive::Some((__key, __handle)) => {
+                            #krate::__derive::Some((__key, __handle)) => {
                                 #optional_skip
-                                return ::deser::__derive::Ok(::deser::__derive::Some(unsafe {
-                                    ::std::mem::transmute::<_, _>((
-                                        __key,
+                                return #krate::__derive::Ok(#krate::__derive::Some(unsafe {
+                                    #krate::__derive::transmute::<_, _>((
+                                        #key,
                                         __handle
                                     ))
                                 }))
                             }
                         }
+                        self.index += 1;
+                        self.nested_emitter_exhausted = true;
+                        // the emitter can hold on to a guard (eg: of a mutex)
+                        // so it has to be dropped before finishing.
+                        self.nested_emitter = #krate::__derive::None;
+                        #krate::ser::Serialize::finish(&self.data.#name, __state)?;
+                        continue;
                     }
                 }
             }
@@ -145,44 +181,95 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
 
     let wrapper_generics = with_lifetime_bound(&input.generics, "'__a");
     let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
-    let bound = syn::parse_quote!(::deser::Serialize);
-    let bounded_where_clause = where_clause_with_bound(&input.generics, bound);
+    let bounded_where_clause = where_clause_with_custom_bound(
+        &input.generics,
+        container_attrs.bound(Direction::Serialize),
+        quote!(#krate::Serialize),
+    );
 
-    Ok(quote! {
-        #[allow(non_upper_case_globals)]
-        const #dummy: () = {
+    let serialize_impl = if let Some(remote) = container_attrs.remote() {
+        let vis = &input.vis;
+        let fieldname = attrs.iter().map(|x| &x.field().ident).collect::<Vec<_>>();
+        quote! {
+            struct __Remote<'__a>(&'__a #remote);
+
+            #[automatically_derived]
+            impl<'__a> #krate::Serialize for __Remote<'__a> {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
+                    &__Descriptor
+                }
+                fn serialize(&self, __state: &#krate::ser::SerializerState) -> #krate::__derive::Result<#krate::ser::Chunk> {
+                    #krate::__derive::Ok(#krate::ser::Chunk::Struct(#krate::__derive::Box::new(__StructEmitter {
+                        data: self.0,
+                        index: 0,
+                        #temp_emitter_init
+                    })))
+                }
+            }
+
+            impl #ident {
+                #[allow(dead_code)]
+                #vis fn serialize_handle(__value: &#remote) -> #krate::ser::SerializeHandle<'_> {
+                    #krate::ser::SerializeHandle::boxed(__Remote(__value))
+                }
+            }
+
+            // converting the mirror ensures it declares the remote's fields with
+            // the same types and keeps its fields from being reported as unused.
+            #[allow(dead_code)]
+            fn __check_remote_fields(__mirror: #ident) -> #remote {
+                #remote { #(#fieldname: __mirror.#fieldname),* }
+            }
+        }
+    } else {
+        quote! {
             #[automatically_derived]
-            impl #impl_generics ::deser::Serialize for #ident #ty_generics #bounded_where_clause {
-                fn descriptor(&self) -> &dyn ::deser::Descriptor {
+            impl #impl_generics #krate::Serialize for #ident #ty_generics #bounded_where_clause {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
                     &__Descriptor
                 }
-                fn serialize(&self, __state: &::deser::ser::SerializerState) -> ::deser::__derive::Result<::deser::ser::Chunk> {
-                    ::deser::__derive::Ok(::deser::ser::Chunk::Struct(Box::new(__StructEmitter {
+                fn serialize(&self, __state: &#krate::ser::SerializerState) -> #krate::__derive::Result<#krate::ser::Chunk> {
+                    #krate::__derive::Ok(#krate::ser::Chunk::Struct(#krate::__derive::Box::new(__StructEmitter {
                         data: self,
                         index: 0,
                         #temp_emitter_init
                     })))
                 }
             }
+        }
+    };
+    let target = match container_attrs.remote() {
+        Some(remote) => quote!(#remote),
+        None => quote!(#ident #ty_generics),
+    };
+
+    Ok(quote! {
+        #[allow(non_upper_case_globals)]
+        const #dummy: () = {
+            #serialize_impl
 
             struct __StructEmitter #wrapper_impl_generics #where_clause {
-                data: &'__a #ident #ty_generics,
+                data: &'__a #target,
                 index: usize,
                 #temp_emitter
             }
 
             struct __Descriptor;
 
-            impl ::deser::Descriptor for __Descriptor {
-                fn name(&self) -> ::deser::__derive::Option<&::deser::__derive::str> {
-                    ::deser::__derive::Some(#type_name)
+            impl #krate::Descriptor for __Descriptor {
+                fn name(&self) -> #krate::__derive::Option<&#krate::__derive::str> {
+                    #krate::__derive::Some(#type_name)
+                }
+
+                fn kind(&self) -> #krate::Kind {
+                    #krate::Kind::Map
                 }
             }
 
             #[automatically_derived]
-            impl #wrapper_impl_generics ::deser::ser::StructEmitter for __StructEmitter #wrapper_ty_generics #bounded_where_clause {
-                fn next(&mut self, __state: &::deser::ser::SerializerState)
-                    -> ::deser::__derive::Result<::deser::__derive::Option<(deser::__derive::StrCow, ::deser::ser::SerializeHandle)>>
+            impl #wrapper_impl_generics #krate::ser::StructEmitter for __StructEmitter #wrapper_ty_generics #bounded_where_clause {
+                fn next(&mut self, __state: &#krate::ser::SerializerState)
+                    -> #krate::__derive::Result<#krate::__derive::Option<(#krate::__derive::StrCow, #krate::ser::SerializeHandle)>>
                 {
                     #[allow(clippy::never_loop)]
                     loop {
@@ -191,7 +278,7 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
                             #(
                                 #state_handler
                             )*
-                            _ => return ::deser::__derive::Ok(::deser::__derive::None),
+                            _ => return #krate::__derive::Ok(#krate::__derive::None),
                         }
                     }
                 }
@@ -200,6 +287,144 @@ fn derive_struct(input: &syn::DeriveInput, fields: &syn::FieldsNamed) -> syn::Re
     })
 }
 
+fn group_skip(krate: &syn::Path, groups: &[String], advance: TokenStream) -> TokenStream {
+    if groups.is_empty() {
+        return quote! {};
+    }
+    quote! {
+        if !__state.get::<#krate::ser::Groups>().is_active(&[#(#groups),*]) {
+            #advance
+            continue;
+        }
+    }
+}
+
+fn derive_as_string(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let dummy = syn::Ident::new(
+        &format!("_DESER_SERIALIZE_IMPL_FOR_{}", ident),
+        Span::call_site(),
+    );
+    let container_attrs = ContainerAttrs::of(input)?;
+    let krate = container_attrs.crate_path();
+    let type_name = container_attrs.container_name(Direction::Serialize);
+    let where_clause = where_clause_with_predicates(
+        &input.generics,
+        container_attrs.bound(Direction::Serialize),
+        vec![syn::parse_quote!(Self: #krate::__derive::Display)],
+    );
+
+    Ok(quote! {
+        #[allow(non_upper_case_globals)]
+        const #dummy: () = {
+            #[automatically_derived]
+            impl #impl_generics #krate::Serialize for #ident #ty_generics #where_clause {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
+                    &__Descriptor
+                }
+                fn serialize(&self, __state: &#krate::ser::SerializerState) -> #krate::__derive::Result<#krate::ser::Chunk> {
+                    #krate::__derive::Ok(#krate::ser::Chunk::Atom(#krate::Atom::Str(
+                        #krate::__derive::Cow::Owned(#krate::__derive::ToString::to_string(self))
+                    )))
+                }
+            }
+
+            struct __Descriptor;
+
+            impl #krate::Descriptor for __Descriptor {
+                fn name(&self) -> #krate::__derive::Option<&#krate::__derive::str> {
+                    #krate::__derive::Some(#type_name)
+                }
+
+                fn kind(&self) -> #krate::Kind {
+                    #krate::Kind::String
+                }
+            }
+        };
+    })
+}
+
+fn derive_seq_struct(
+    input: &syn::DeriveInput,
+    fields: &syn::FieldsNamed,
+    container_attrs: &ContainerAttrs,
+) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let dummy = syn::Ident::new(
+        &format!("_DESER_SERIALIZE_IMPL_FOR_{}", ident),
+        Span::call_site(),
+    );
+    let krate = container_attrs.crate_path();
+    let type_name = container_attrs.container_name(Direction::Serialize);
+
+    for field in &fields.named {
+        ensure_no_field_attrs(field)?;
+    }
+    let fieldname = fields.named.iter().map(|x| &x.ident).collect::<Vec<_>>();
+    let index = 0..fieldname.len();
+
+    let wrapper_generics = with_lifetime_bound(&input.generics, "'__a");
+    let (wrapper_impl_generics, wrapper_ty_generics, _) = wrapper_generics.split_for_impl();
+    let bounded_where_clause = where_clause_with_custom_bound(
+        &input.generics,
+        container_attrs.bound(Direction::Serialize),
+        quote!(#krate::Serialize),
+    );
+
+    Ok(quote! {
+        #[allow(non_upper_case_globals)]
+        const #dummy: () = {
+            #[automatically_derived]
+            impl #impl_generics #krate::Serialize for #ident #ty_generics #bounded_where_clause {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
+                    &__Descriptor
+                }
+                fn serialize(&self, __state: &#krate::ser::SerializerState) -> #krate::__derive::Result<#krate::ser::Chunk> {
+                    #krate::__derive::Ok(#krate::ser::Chunk::Seq(#krate::__derive::Box::new(__SeqEmitter {
+                        data: self,
+                        index: 0,
+                    })))
+                }
+            }
+
+            struct __SeqEmitter #wrapper_impl_generics #where_clause {
+                data: &'__a #ident #ty_generics,
+                index: usize,
+            }
+
+            struct __Descriptor;
+
+            impl #krate::Descriptor for __Descriptor {
+                fn name(&self) -> #krate::__derive::Option<&#krate::__derive::str> {
+                    #krate::__derive::Some(#type_name)
+                }
+
+                fn kind(&self) -> #krate::Kind {
+                    #krate::Kind::Seq
+                }
+            }
+
+            #[automatically_derived]
+            impl #wrapper_impl_generics #krate::ser::SeqEmitter for __SeqEmitter #wrapper_ty_generics #bounded_where_clause {
+                fn next(&mut self, __state: &#krate::ser::SerializerState)
+                    -> #krate::__derive::Result<#krate::__derive::Option<#krate::ser::SerializeHandle>>
+                {
+                    let __index = self.index;
+                    self.index += 1;
+                    #krate::__derive::Ok(match __index {
+                        #(
+                            #index => #krate::__derive::Some(#krate::ser::SerializeHandle::to(&self.data.#fieldname)),
+                        )*
+                        _ => #krate::__derive::None,
+                    })
+                }
+            }
+        };
+    })
+}
+
 fn derive_enum(input: &syn::DeriveInput, enumeration: &syn::DataEnum) -> syn::Result<TokenStream> {
     if input.generics.lt_token.is_some() || input.generics.where_clause.is_some() {
         return Err(syn::Error::new(
@@ -215,44 +440,87 @@ fn derive_enum(input: &syn::DeriveInput, enumeration: &syn::DataEnum) -> syn::Re
     );
 
     let container_attrs = ContainerAttrs::of(input)?;
-    let var_idents = enumeration
-        .variants
-        .iter()
-        .map(|variant| match variant.fields {
-            syn::Fields::Unit => Ok(&variant.ident),
-            _ => Err(syn::Error::new_spanned(
-                variant,
-                "Invalid variant: only simple enum variants without fields are supported",
-            )),
-        })
-        .collect::<syn::Result<Vec<_>>>()?;
+    let krate = container_attrs.crate_path();
     let attrs = enumeration
         .variants
         .iter()
         .map(EnumVariantAttrs::of)
         .collect::<syn::Result<Vec<_>>>()?;
+    ensure_valid_other_variant(&container_attrs, &attrs)?;
+    let type_name = container_attrs.container_name(Direction::Serialize);
+    let kind = if container_attrs.repr().serialize_as_int() {
+        quote!(#krate::Kind::Int)
+    } else {
+        quote!(#krate::Kind::String)
+    };
+    let var_idents = attrs
+        .iter()
+        .filter(|x| !x.captures_other())
+        .map(|x| &x.variant().ident)
+        .collect::<Vec<_>>();
     let names = attrs
         .iter()
-        .map(|x| x.name(&container_attrs))
+        .filter(|x| !x.captures_other())
+        .map(|x| x.name(&container_attrs, Direction::Serialize))
+        .collect::<Vec<_>>();
+    let atoms = var_idents
+        .iter()
+        .zip(names.iter())
+        .map(|(var_ident, name)| {
+            if container_attrs.repr().serialize_as_int() {
+                quote! {
+                    #krate::__derive::discriminant_to_atom(#ident::#var_ident as i128)
+                }
+            } else {
+                quote! {
+                    #krate::Atom::Str(#krate::__derive::Cow::Borrowed(#name))
+                }
+            }
+        })
         .collect::<Vec<_>>();
+    let other_handler = attrs.iter().find(|x| x.captures_other()).map(|x| {
+        let var_ident = &x.variant().ident;
+        quote! {
+            #ident::#var_ident(ref __value) => {
+                return #krate::ser::Serialize::serialize(__value, __state);
+            }
+        }
+    });
 
     Ok(quote! {
         #[allow(non_upper_case_globals)]
         const #dummy: () = {
             #[automatically_derived]
-            impl ::deser::Serialize for #ident {
-                fn serialize(&self, __state: &::deser::ser::SerializerState)
-                    -> ::deser::__derive::Result<::deser::ser::Chunk>
+            impl #krate::Serialize for #ident {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
+                    &__Descriptor
+                }
+
+                fn serialize(&self, __state: &#krate::ser::SerializerState)
+                    -> #krate::__derive::Result<#krate::ser::Chunk>
                 {
-                    ::deser::__derive::Ok(match *self {
+                    #krate::__derive::Ok(match *self {
                         #(
                             #ident::#var_idents => {
-                                ::deser::ser::Chunk::Atom(::deser::Atom::Str(::deser::__derive::Cow::Borrowed(#names)))
+                                #krate::ser::Chunk::Atom(#atoms)
                             }
                         )*
+                        #other_handler
                     })
                 }
             }
+
+            struct __Descriptor;
+
+            impl #krate::Descriptor for __Descriptor {
+                fn name(&self) -> #krate::__derive::Option<&#krate::__derive::str> {
+                    #krate::__derive::Some(#type_name)
+                }
+
+                fn kind(&self) -> #krate::Kind {
+                    #kind
+                }
+            }
         };
     })
 }
@@ -268,29 +536,33 @@ fn derive_newtype_struct(input: &syn::DeriveInput, field: &syn::Field) -> syn::R
     // TODO: we want to report the type name here but the current descriptor
     // interface does not let us.  https://github.com/mitsuhiko/deser/issues/8
     let container_attrs = ContainerAttrs::of(input)?;
-    let _type_name = container_attrs.container_name();
+    let krate = container_attrs.crate_path();
+    let _type_name = container_attrs.container_name(Direction::Serialize);
 
     ensure_no_field_attrs(field)?;
 
-    let bound = syn::parse_quote!(::deser::Serialize);
-    let bounded_where_clause = where_clause_with_bound(&input.generics, bound);
+    let bounded_where_clause = where_clause_with_custom_bound(
+        &input.generics,
+        container_attrs.bound(Direction::Serialize),
+        quote!(#krate::Serialize),
+    );
 
     Ok(quote! {
         #[allow(non_upper_case_globals)]
         const #dummy: () = {
             #[automatically_derived]
-            impl #impl_generics ::deser::Serialize for #ident #ty_generics #bounded_where_clause {
-                fn descriptor(&self) -> &dyn ::deser::Descriptor {
+            impl #impl_generics #krate::Serialize for #ident #ty_generics #bounded_where_clause {
+                fn descriptor(&self) -> &dyn #krate::Descriptor {
                     self.0.descriptor()
                 }
-                fn serialize(&self, __state: &::deser::ser::SerializerState) -> ::deser::__derive::Result<::deser::ser::Chunk> {
-                    ::deser::ser::Serialize::serialize(&self.0, __state)
+                fn serialize(&self, __state: &#krate::ser::SerializerState) -> #krate::__derive::Result<#krate::ser::Chunk> {
+                    #krate::ser::Serialize::serialize(&self.0, __state)
                 }
-                fn finish(&self, __state: &::deser::ser::SerializerState) -> ::deser::__derive::Result<()> {
-                    ::deser::ser::Serialize::finish(&self.0, __state)
+                fn finish(&self, __state: &#krate::ser::SerializerState) -> #krate::__derive::Result<()> {
+                    #krate::ser::Serialize::finish(&self.0, __state)
                 }
                 fn is_optional(&self) -> bool {
-                    ::deser::ser::Serialize::is_optional(&self.0)
+                    #krate::ser::Serialize::is_optional(&self.0)
                 }
             }
         };
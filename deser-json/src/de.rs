@@ -1,6 +1,6 @@
 use std::str;
 
-use deser::de::{Deserialize, DeserializeDriver};
+use deser::de::{Deserialize, DeserializeDriver, DeserializerState};
 use deser::Atom;
 use deser::Event;
 use deser::{Error, ErrorKind};
@@ -31,6 +31,7 @@ pub struct Deserializer<'a> {
     input: &'a [u8],
     pos: usize,
     buffer: Vec<u8>,
+    state: Option<DeserializerState<'a>>,
 }
 
 enum ContainerState {
@@ -41,10 +42,28 @@ enum ContainerState {
 impl<'a> Deserializer<'a> {
     /// Creates a new deserializer.
     pub fn new(input: &'a [u8]) -> Deserializer<'a> {
+        Deserializer::with_state(input, DeserializerState::builder().build())
+    }
+
+    /// Creates a new deserializer with a pre-populated state.
+    ///
+    /// ```rust
+    /// use deser::de::{DeserializerState, DuplicateKeys};
+    /// use std::collections::HashMap;
+    ///
+    /// let state = DeserializerState::builder()
+    ///     .insert(DuplicateKeys::Reject)
+    ///     .build();
+    /// let rv = deser_json::Deserializer::with_state(br#"{"a": 1, "a": 2}"#, state)
+    ///     .deserialize::<HashMap<String, u32>>();
+    /// assert!(rv.is_err());
+    /// ```
+    pub fn with_state(input: &'a [u8], state: DeserializerState<'a>) -> Deserializer<'a> {
         Deserializer {
             input,
             pos: 0,
             buffer: Vec::new(),
+            state: Some(state),
         }
     }
 
@@ -52,8 +71,14 @@ impl<'a> Deserializer<'a> {
     pub fn deserialize<T: Deserialize>(&mut self) -> Result<T, Error> {
         let mut out = None;
         {
-            let mut driver = DeserializeDriver::new(&mut out);
-            self.deserialize_into(&mut driver)?;
+            let state = self
+                .state
+                .take()
+                .unwrap_or_else(|| DeserializerState::builder().build());
+            let mut driver = DeserializeDriver::with_state(&mut out, state);
+            let rv = self.deserialize_into(&mut driver);
+            self.state = Some(driver.into_state());
+            rv?;
         }
         out.take()
             .ok_or_else(|| Error::new(ErrorKind::EndOfFile, "empty input"))
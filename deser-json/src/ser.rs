@@ -1,9 +1,10 @@
-use deser::ser::SerializeDriver;
+use deser::ser::{SerializeDriver, SerializerState};
 use deser::{Atom, Error, ErrorKind, Event, Serialize};
 
 /// Serializes a serializable to JSON.
 pub struct Serializer {
     out: String,
+    state: SerializerState<'static>,
 }
 
 enum ContainerState {
@@ -20,12 +21,33 @@ impl Default for Serializer {
 impl Serializer {
     /// Creates a new serializer that writes into the given writer.
     pub fn new() -> Serializer {
-        Serializer { out: String::new() }
+        Serializer::with_state(SerializerState::builder().build())
+    }
+
+    /// Creates a new serializer with a pre-populated state.
+    ///
+    /// ```rust
+    /// use deser::ser::{Groups, SerializerState};
+    ///
+    /// let state = SerializerState::builder()
+    ///     .insert(Groups::new(["public"]))
+    ///     .build();
+    /// let json = deser_json::Serializer::with_state(state)
+    ///     .serialize(&vec![1, 2])
+    ///     .unwrap();
+    /// assert_eq!(json, "[1,2]");
+    /// ```
+    pub fn with_state(state: SerializerState<'static>) -> Serializer {
+        Serializer {
+            out: String::new(),
+            state,
+        }
     }
 
     /// Serializes the given value.
     pub fn serialize(mut self, value: &dyn Serialize) -> Result<String, Error> {
-        let mut driver = SerializeDriver::new(value);
+        let state = std::mem::replace(&mut self.state, SerializerState::builder().build());
+        let mut driver = SerializeDriver::with_state(value, state);
         let mut container_stack = Vec::new();
 
         macro_rules! unsupported {
@@ -76,8 +98,14 @@ impl Serializer {
                 if is_key {
                     match event {
                         Event::Atom(Atom::Str(val)) => self.write_escaped_str(&val),
-                        Event::Atom(Atom::Char(c)) => {
-                            self.write_escaped_str(&(c as u32).to_string())
+                        Event::Atom(Atom::Char(c)) => self.write_escaped_str(&c.to_string()),
+                        Event::Atom(Atom::U64(val)) => self.write_escaped_str(&val.to_string()),
+                        Event::Atom(Atom::I64(val)) => self.write_escaped_str(&val.to_string()),
+                        Event::Atom(Atom::F64(val)) if val.is_finite() => {
+                            self.write_escaped_str(&val.to_string())
+                        }
+                        Event::Atom(Atom::Bool(val)) => {
+                            self.write_escaped_str(if val { "true" } else { "false" })
                         }
                         _ => unsupported!("JSON does not support this value for map keys"),
                     }
@@ -47,3 +47,43 @@ fn test_flatten() {
         }
     )
 }
+
+#[test]
+fn test_non_string_keys() {
+    use std::collections::BTreeMap;
+
+    let map: BTreeMap<i32, bool> = from_str(r#"{"1": true, "-2": false}"#).unwrap();
+    assert_eq!(map, vec![(1, true), (-2, false)].into_iter().collect());
+}
+
+#[test]
+fn test_with_state() {
+    use deser::de::{Coercion, DeserializerState};
+    use deser_json::Deserializer;
+
+    let state = DeserializerState::builder()
+        .insert(Coercion::Lenient)
+        .build();
+    let mut de = Deserializer::with_state(br#"["1", "2"]"#, state);
+    let x: Vec<u32> = de.deserialize().unwrap();
+    assert_eq!(x, vec![1, 2]);
+
+    assert!(from_str::<Vec<u32>>(r#"["1", "2"]"#).is_err());
+}
+
+#[test]
+fn test_non_string_keys_round_trip() {
+    use std::collections::BTreeMap;
+
+    let map: BTreeMap<char, i32> = vec![('a', 1), ('7', 2)].into_iter().collect();
+    let json = deser_json::to_string(&map).unwrap();
+    assert_eq!(from_str::<BTreeMap<char, i32>>(&json).unwrap(), map);
+
+    let map: BTreeMap<i64, bool> = vec![(-1, true), (42, false)].into_iter().collect();
+    let json = deser_json::to_string(&map).unwrap();
+    assert_eq!(from_str::<BTreeMap<i64, bool>>(&json).unwrap(), map);
+
+    let map: BTreeMap<bool, u8> = vec![(false, 0), (true, 1)].into_iter().collect();
+    let json = deser_json::to_string(&map).unwrap();
+    assert_eq!(from_str::<BTreeMap<bool, u8>>(&json).unwrap(), map);
+}
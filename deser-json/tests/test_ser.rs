@@ -36,3 +36,43 @@ fn test_flatten() {
         r#"{"id":42,"is_active":true,"is_admin":true,"flags":["german","staff"]}"#
     );
 }
+
+#[test]
+fn test_non_string_keys() {
+    use std::collections::BTreeMap;
+
+    let map: BTreeMap<u32, bool> = vec![(1, true), (2, false)].into_iter().collect();
+    assert_eq!(to_string(&map).unwrap(), r#"{"1":true,"2":false}"#);
+
+    let map: BTreeMap<bool, i32> = vec![(false, -1)].into_iter().collect();
+    assert_eq!(to_string(&map).unwrap(), r#"{"false":-1}"#);
+
+    let map: BTreeMap<char, i32> = vec![('a', 1)].into_iter().collect();
+    assert_eq!(to_string(&map).unwrap(), r#"{"a":1}"#);
+}
+
+#[test]
+fn test_with_state() {
+    use deser::ser::{Groups, SerializerState};
+    use deser_json::Serializer;
+
+    #[derive(Serialize)]
+    struct User {
+        id: u64,
+        #[deser(groups = "admin")]
+        email: String,
+    }
+
+    let user = User {
+        id: 1,
+        email: "user@example.com".into(),
+    };
+    let state = SerializerState::builder()
+        .insert(Groups::new(["public"]))
+        .build();
+    let json = Serializer::with_state(state).serialize(&user).unwrap();
+    assert_eq!(json, r#"{"id":1}"#);
+
+    let json = Serializer::new().serialize(&user).unwrap();
+    assert_eq!(json, r#"{"id":1,"email":"user@example.com"}"#);
+}
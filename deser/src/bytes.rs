@@ -0,0 +1,234 @@
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+
+use crate::de::{Deserialize, DeserializerState, Sink, SinkHandle};
+use crate::descriptors::{Descriptor, Kind, NamedDescriptor};
+use crate::error::Error;
+use crate::event::Atom;
+use crate::ser::{Chunk, Serialize, SerializerState};
+
+/// A borrowed byte buffer that is always serialized as bytes.
+///
+/// Only `Vec<u8>`, `&[u8]` and `[u8; N]` are automatically serialized as
+/// [`Atom::Bytes`].  This wrapper is useful for other byte containers or in
+/// generic code where that specialization does not apply.  On
+/// deserialization bytes, strings and sequences of integers are accepted.
+/// As deserialization cannot borrow from the input, the deserialized value
+/// always owns its data.
+///
+/// ```rust
+/// use deser::Bytes;
+///
+/// let bytes = Bytes::new(b"hello");
+/// assert_eq!(&*bytes, b"hello");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Bytes<'a> {
+    bytes: Cow<'a, [u8]>,
+}
+
+impl<'a> Bytes<'a> {
+    /// Wraps a borrowed byte slice.
+    pub fn new(bytes: &'a [u8]) -> Bytes<'a> {
+        Bytes {
+            bytes: Cow::Borrowed(bytes),
+        }
+    }
+
+    /// Converts the bytes into an owned [`ByteBuf`].
+    pub fn into_owned(self) -> ByteBuf {
+        ByteBuf::from(self.bytes.into_owned())
+    }
+}
+
+impl<'a> Deref for Bytes<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+impl<'a> AsRef<[u8]> for Bytes<'a> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<'a> From<&'a [u8]> for Bytes<'a> {
+    fn from(bytes: &'a [u8]) -> Bytes<'a> {
+        Bytes::new(bytes)
+    }
+}
+
+/// An owned byte buffer that is always serialized as bytes.
+///
+/// This is the owned version of [`Bytes`].
+///
+/// ```rust
+/// use deser::ByteBuf;
+///
+/// let mut buf = ByteBuf::new();
+/// buf.extend_from_slice(b"hello");
+/// assert_eq!(buf.into_vec(), b"hello".to_vec());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ByteBuf {
+    bytes: Vec<u8>,
+}
+
+impl ByteBuf {
+    /// Creates an empty byte buffer.
+    pub fn new() -> ByteBuf {
+        ByteBuf::default()
+    }
+
+    /// Unwraps the underlying vector.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl Deref for ByteBuf {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+impl DerefMut for ByteBuf {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.bytes
+    }
+}
+
+impl AsRef<[u8]> for ByteBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl From<Vec<u8>> for ByteBuf {
+    fn from(bytes: Vec<u8>) -> ByteBuf {
+        ByteBuf { bytes }
+    }
+}
+
+impl From<ByteBuf> for Vec<u8> {
+    fn from(buf: ByteBuf) -> Vec<u8> {
+        buf.bytes
+    }
+}
+
+static BYTES_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+    name: "Bytes",
+    kind: Kind::Any,
+};
+static BYTE_BUF_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+    name: "ByteBuf",
+    kind: Kind::Any,
+};
+
+impl<'a> Serialize for Bytes<'a> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        &BYTES_DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(Chunk::Atom(Atom::Bytes(Cow::Borrowed(&self.bytes))))
+    }
+}
+
+impl Serialize for ByteBuf {
+    fn descriptor(&self) -> &dyn Descriptor {
+        &BYTE_BUF_DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(Chunk::Atom(Atom::Bytes(Cow::Borrowed(&self.bytes))))
+    }
+}
+
+impl<'a> Deserialize for Bytes<'a> {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        SinkHandle::boxed(BytesSink {
+            slot: out,
+            descriptor: &BYTES_DESCRIPTOR,
+            wrap: |bytes| Bytes {
+                bytes: Cow::Owned(bytes),
+            },
+            buf: Vec::new(),
+            element: None,
+            is_seq: false,
+        })
+    }
+}
+
+impl Deserialize for ByteBuf {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        SinkHandle::boxed(BytesSink {
+            slot: out,
+            descriptor: &BYTE_BUF_DESCRIPTOR,
+            wrap: ByteBuf::from,
+            buf: Vec::new(),
+            element: None,
+            is_seq: false,
+        })
+    }
+}
+
+struct BytesSink<'a, T> {
+    slot: &'a mut Option<T>,
+    descriptor: &'static NamedDescriptor,
+    wrap: fn(Vec<u8>) -> T,
+    buf: Vec<u8>,
+    element: Option<u8>,
+    is_seq: bool,
+}
+
+impl<'a, T> BytesSink<'a, T> {
+    fn flush(&mut self) {
+        if let Some(element) = self.element.take() {
+            self.buf.push(element);
+        }
+    }
+}
+
+impl<'a, T> Sink for BytesSink<'a, T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        self.descriptor
+    }
+
+    fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+        match atom {
+            Atom::Bytes(value) => {
+                *self.slot = Some((self.wrap)(value.into_owned()));
+                Ok(())
+            }
+            Atom::Str(value) => {
+                *self.slot = Some((self.wrap)(value.into_owned().into_bytes()));
+                Ok(())
+            }
+            other => self.unexpected_atom(other, state),
+        }
+    }
+
+    fn seq(&mut self, _state: &DeserializerState) -> Result<(), Error> {
+        self.is_seq = true;
+        Ok(())
+    }
+
+    fn next_value(&mut self, _state: &DeserializerState) -> Result<SinkHandle, Error> {
+        self.flush();
+        Ok(Deserialize::deserialize_into(&mut self.element))
+    }
+
+    fn finish(&mut self, _state: &DeserializerState) -> Result<(), Error> {
+        if self.is_seq {
+            self.flush();
+            *self.slot = Some((self.wrap)(std::mem::take(&mut self.buf)));
+        }
+        Ok(())
+    }
+}
@@ -4,7 +4,6 @@ use crate::de::{Deserialize, DeserializerState, SinkHandle};
 use crate::descriptors::Descriptor;
 use crate::error::Error;
 use crate::event::Event;
-use crate::extensions::Extensions;
 
 /// The driver allows emitting deserialization events into a [`Deserialize`].
 ///
@@ -15,6 +14,7 @@ use crate::extensions::Extensions;
 pub struct DeserializeDriver<'a> {
     state: DeserializerState<'a>,
     current_sink: Option<SinkHandle<'static>>,
+    current_sink_ready: bool,
     sink_stack: ManuallyDrop<Vec<(SinkHandle<'static>, Layer)>>,
 }
 
@@ -31,15 +31,34 @@ impl<'a> DeserializeDriver<'a> {
         DeserializeDriver::from_sink(T::deserialize_into(out))
     }
 
+    /// Creates a new deserializer driver with a pre-populated state.
+    ///
+    /// See [`DeserializerState::builder`] for how to create the state.
+    pub fn with_state<T: Deserialize>(
+        out: &'a mut Option<T>,
+        state: DeserializerState<'a>,
+    ) -> DeserializeDriver<'a> {
+        DeserializeDriver::from_sink_with_state(T::deserialize_into(out), state)
+    }
+
     /// Creates a new deserializer driver from a sink.
     pub fn from_sink(sink: SinkHandle) -> DeserializeDriver<'a> {
+        DeserializeDriver::from_sink_with_state(sink, DeserializerState::builder().build())
+    }
+
+    /// Creates a new deserializer driver from a sink with a pre-populated state.
+    pub fn from_sink_with_state(
+        sink: SinkHandle,
+        mut state: DeserializerState<'a>,
+    ) -> DeserializeDriver<'a> {
+        state.descriptor_stack.clear();
+        state.descriptor_stack.reserve(STACK_CAPACITY);
+        state.set_key(false);
         DeserializeDriver {
-            state: DeserializerState {
-                extensions: Extensions::default(),
-                descriptor_stack: Vec::with_capacity(STACK_CAPACITY),
-            },
+            state,
             sink_stack: ManuallyDrop::new(Vec::with_capacity(STACK_CAPACITY)),
             current_sink: Some(unsafe { extend_lifetime!(sink, SinkHandle<'_>) }),
+            current_sink_ready: false,
         }
     }
 
@@ -48,6 +67,47 @@ impl<'a> DeserializeDriver<'a> {
         &self.state
     }
 
+    /// Consumes the driver and returns the extensions as a new state.
+    ///
+    /// This can be used to carry extension values over to another driver.
+    pub fn into_state(mut self) -> DeserializerState<'static> {
+        DeserializerState {
+            extensions: std::mem::take(&mut self.state.extensions),
+            descriptor_stack: Vec::new(),
+            is_key: Default::default(),
+        }
+    }
+
+    /// Returns the descriptor of the sink that receives the next value.
+    ///
+    /// This lets formats with untyped scalars parse values depending on the
+    /// target type.  [`Descriptor::kind`] returns the coarse kind of value
+    /// the sink expects, [`Kind::Any`](crate::Kind::Any) if it accepts
+    /// different kinds of values.
+    ///
+    /// Within a map or sequence this creates the sink for the next key or
+    /// value.  If the container ends instead, that sink is discarded before
+    /// the container's sink is finished.
+    ///
+    /// ```rust
+    /// use deser::{Event, Kind};
+    /// use deser::de::DeserializeDriver;
+    ///
+    /// let mut out = None::<Vec<u32>>;
+    /// let mut driver = DeserializeDriver::new(&mut out);
+    /// assert_eq!(driver.expected().unwrap().kind(), Kind::Seq);
+    /// driver.emit(Event::SeqStart).unwrap();
+    /// assert_eq!(driver.expected().unwrap().kind(), Kind::Int);
+    /// ```
+    pub fn expected(&mut self) -> Result<&dyn Descriptor, Error> {
+        self.prepare_current_sink()?;
+        Ok(self
+            .current_sink
+            .as_ref()
+            .expect("no active sink")
+            .descriptor())
+    }
+
     /// Emits an event into the driver.
     ///
     /// # Panics
@@ -58,6 +118,24 @@ impl<'a> DeserializeDriver<'a> {
         self._emit(event.into())
     }
 
+    fn prepare_current_sink(&mut self) -> Result<(), Error> {
+        if !self.current_sink_ready {
+            self.update_current_sink()?;
+            self.current_sink_ready = true;
+        }
+        Ok(())
+    }
+
+    fn discard_prepared_sink(&mut self) {
+        // a sink prepared by `expected` borrows from the container sink and
+        // must be gone before the container is finished.
+        if self.current_sink_ready {
+            self.current_sink = None;
+            self.current_sink_ready = false;
+        }
+        self.state.set_key(false);
+    }
+
     fn update_current_sink(&mut self) -> Result<(), Error> {
         match self.sink_stack.last_mut() {
             Some((map_sink, Layer::Map(ref mut is_key))) => {
@@ -66,10 +144,12 @@ impl<'a> DeserializeDriver<'a> {
                 } else {
                     map_sink.next_value(&self.state)?
                 };
+                self.state.set_key(*is_key);
                 *is_key = !*is_key;
                 self.current_sink = Some(unsafe { extend_lifetime!(next_sink, SinkHandle<'_>) });
             }
             Some((seq_sink, Layer::Seq)) => {
+                self.state.set_key(false);
                 self.current_sink = Some(unsafe {
                     extend_lifetime!(seq_sink.next_value(&self.state)?, SinkHandle<'_>)
                 });
@@ -82,7 +162,8 @@ impl<'a> DeserializeDriver<'a> {
     fn _emit(&mut self, event: Event) -> Result<(), Error> {
         macro_rules! current_sink {
             () => {{
-                self.update_current_sink()?;
+                self.prepare_current_sink()?;
+                self.current_sink_ready = false;
                 self.current_sink.as_mut().expect("no active sink")
             }};
         }
@@ -106,9 +187,11 @@ impl<'a> DeserializeDriver<'a> {
             }
             Event::MapEnd => match self.sink_stack.pop() {
                 Some((mut map_sink, Layer::Map(_))) => {
+                    self.discard_prepared_sink();
                     map_sink.finish(&self.state)?;
                     self.state.descriptor_stack.pop();
                     self.current_sink = Some(map_sink);
+                    self.current_sink_ready = false;
                 }
                 _ => panic!("not inside a MapSink"),
             },
@@ -125,9 +208,11 @@ impl<'a> DeserializeDriver<'a> {
             }
             Event::SeqEnd => match self.sink_stack.pop() {
                 Some((mut seq_sink, Layer::Seq)) => {
+                    self.discard_prepared_sink();
                     seq_sink.finish(&self.state)?;
                     self.state.descriptor_stack.pop();
                     self.current_sink = Some(seq_sink);
+                    self.current_sink_ready = false;
                 }
                 _ => panic!("not inside a SeqSink"),
             },
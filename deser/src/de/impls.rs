@@ -1,10 +1,31 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::ffi::OsString;
 use std::hash::BuildHasher;
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::mem::{take, MaybeUninit};
-
-use crate::de::{Deserialize, DeserializerState, OwnedSink, Sink, SinkHandle};
-use crate::descriptors::{Descriptor, NamedDescriptor, UnorderedNamedDescriptor};
+use std::net::{
+    AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6,
+};
+use std::num::{
+    IntErrorKind, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, ParseIntError, Saturating, Wrapping,
+};
+use std::ops::{Bound, Range, RangeInclusive};
+use std::path::PathBuf;
+use std::rc::{self, Rc};
+use std::str::FromStr;
+use std::sync::{self, Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::de::{
+    Coercion, Deserialize, DeserializerState, DuplicateKeys, OwnedSink, Sink, SinkHandle,
+};
+use crate::descriptors::{Descriptor, Kind, NamedDescriptor, UnorderedNamedDescriptor};
 use crate::error::{Error, ErrorKind};
 use crate::event::Atom;
 
@@ -22,7 +43,10 @@ macro_rules! deserialize {
 
 impl Sink for SlotWrapper<()> {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "null" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "null",
+            kind: Kind::Any,
+        };
         &DESCRIPTOR
     }
 
@@ -40,7 +64,10 @@ deserialize!(());
 
 impl Sink for SlotWrapper<bool> {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "bool" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "bool",
+            kind: Kind::Bool,
+        };
         &DESCRIPTOR
     }
 
@@ -50,6 +77,10 @@ impl Sink for SlotWrapper<bool> {
                 **self = Some(value);
                 Ok(())
             }
+            Atom::Str(ref value) if coerces_str(state) => {
+                **self = Some(parse_str(value, "bool", state)?);
+                Ok(())
+            }
             other => self.unexpected_atom(other, state),
         }
     }
@@ -58,7 +89,10 @@ deserialize!(bool);
 
 impl Sink for SlotWrapper<String> {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "string" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "string",
+            kind: Kind::String,
+        };
         &DESCRIPTOR
     }
 
@@ -74,12 +108,45 @@ impl Sink for SlotWrapper<String> {
 }
 deserialize!(String);
 
+// strings are parsed into scalars in key position or if coercion is enabled.
+fn coerces_str(state: &DeserializerState) -> bool {
+    state.is_key() || *state.get::<Coercion>() == Coercion::Lenient
+}
+
+// parses a string into a number or bool.
+fn parse_str<T>(value: &str, type_name: &str, state: &DeserializerState) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    value.parse().map_err(|err: T::Err| {
+        let what = if state.is_key() { "key" } else { "value" };
+        let int_error = (&err as &dyn Any).downcast_ref::<ParseIntError>();
+        if matches!(
+            int_error.map(ParseIntError::kind),
+            Some(IntErrorKind::PosOverflow | IntErrorKind::NegOverflow)
+        ) {
+            Error::new(
+                ErrorKind::OutOfRange,
+                format!("{} {} '{}' out of range for type", type_name, what, value),
+            )
+        } else {
+            Error::new(
+                ErrorKind::Unexpected,
+                format!("invalid {} {} '{}'", type_name, what, value),
+            )
+        }
+        .with_source(err)
+    })
+}
+
 macro_rules! int_sink {
     ($ty:ty) => {
         impl Sink for SlotWrapper<$ty> {
             fn descriptor(&self) -> &dyn Descriptor {
                 static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
                     name: stringify!($ty),
+                    kind: Kind::Int,
                 };
                 &DESCRIPTOR
             }
@@ -110,6 +177,10 @@ macro_rules! int_sink {
                             ))
                         }
                     }
+                    Atom::Str(ref value) if coerces_str(state) => {
+                        **self = Some(parse_str(value, stringify!($ty), state)?);
+                        Ok(())
+                    }
                     other => self.unexpected_atom(other, state),
                 }
             }
@@ -150,7 +221,10 @@ deserialize!(usize);
 
 impl Sink for SlotWrapper<char> {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "char" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "char",
+            kind: Kind::String,
+        };
         &DESCRIPTOR
     }
 
@@ -182,6 +256,7 @@ macro_rules! float_sink {
             fn descriptor(&self) -> &dyn Descriptor {
                 static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
                     name: stringify!($ty),
+                    kind: Kind::Float,
                 };
                 &DESCRIPTOR
             }
@@ -200,6 +275,10 @@ macro_rules! float_sink {
                         **self = Some(value as $ty);
                         Ok(())
                     }
+                    Atom::Str(ref value) if coerces_str(state) => {
+                        **self = Some(parse_str(value, stringify!($ty), state)?);
+                        Ok(())
+                    }
                     other => self.unexpected_atom(other, state),
                 }
             }
@@ -232,8 +311,14 @@ impl<T: Deserialize> Deserialize for Vec<T> {
 
         impl<'a, T: Deserialize> Sink for VecSink<'a, T> {
             fn descriptor(&self) -> &dyn Descriptor {
-                static SLICE_DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "vec" };
-                static BYTES_DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "bytes" };
+                static SLICE_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+                    name: "vec",
+                    kind: Kind::Seq,
+                };
+                static BYTES_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+                    name: "bytes",
+                    kind: Kind::Any,
+                };
                 if unsafe { T::__private_is_bytes() } {
                     &BYTES_DESCRIPTOR
                 } else {
@@ -286,6 +371,33 @@ impl<T: Deserialize> Deserialize for Vec<T> {
     }
 }
 
+// feeds a string key into a key slot as if it was in key position.
+fn deserialize_key<K: Deserialize>(
+    slot: &mut Option<K>,
+    key: &str,
+    state: &DeserializerState,
+) -> Result<(), Error> {
+    let was_key = state.set_key(true);
+    let rv = (|| {
+        let mut key_sink = Deserialize::deserialize_into(slot);
+        key_sink.atom(Atom::Str(key.into()), state)?;
+        key_sink.finish(state)
+    })();
+    state.set_key(was_key);
+    rv
+}
+
+fn check_duplicate_key(state: &DeserializerState) -> Result<(), Error> {
+    if *state.get::<DuplicateKeys>() == DuplicateKeys::Reject {
+        Err(Error::new(
+            ErrorKind::DuplicateField,
+            "Duplicate key in map",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 impl<K, V> Deserialize for BTreeMap<K, V>
 where
     K: Ord + Deserialize,
@@ -303,10 +415,13 @@ where
         where
             K: Ord,
         {
-            fn flush(&mut self) {
+            fn flush(&mut self, state: &DeserializerState) -> Result<(), Error> {
                 if let (Some(key), Some(value)) = (self.key.take(), self.value.take()) {
-                    self.map.insert(key, value);
+                    if self.map.insert(key, value).is_some() {
+                        check_duplicate_key(state)?;
+                    }
                 }
+                Ok(())
             }
         }
 
@@ -316,8 +431,10 @@ where
             V: Deserialize,
         {
             fn descriptor(&self) -> &dyn Descriptor {
-                static DESCRIPTOR: UnorderedNamedDescriptor =
-                    UnorderedNamedDescriptor { name: "map" };
+                static DESCRIPTOR: UnorderedNamedDescriptor = UnorderedNamedDescriptor {
+                    name: "map",
+                    kind: Kind::Map,
+                };
                 &DESCRIPTOR
             }
 
@@ -325,8 +442,8 @@ where
                 Ok(())
             }
 
-            fn next_key(&mut self, _state: &DeserializerState) -> Result<SinkHandle, Error> {
-                self.flush();
+            fn next_key(&mut self, state: &DeserializerState) -> Result<SinkHandle, Error> {
+                self.flush(state)?;
                 Ok(Deserialize::deserialize_into(&mut self.key))
             }
 
@@ -334,8 +451,18 @@ where
                 Ok(Deserialize::deserialize_into(&mut self.value))
             }
 
-            fn finish(&mut self, _state: &DeserializerState) -> Result<(), Error> {
-                self.flush();
+            fn value_for_key(
+                &mut self,
+                key: &str,
+                state: &DeserializerState,
+            ) -> Result<Option<SinkHandle>, Error> {
+                self.flush(state)?;
+                deserialize_key(&mut self.key, key, state)?;
+                Ok(Some(Deserialize::deserialize_into(&mut self.value)))
+            }
+
+            fn finish(&mut self, state: &DeserializerState) -> Result<(), Error> {
+                self.flush(state)?;
                 *self.slot = Some(take(&mut self.map));
                 Ok(())
             }
@@ -369,10 +496,13 @@ where
             K: Hash + Eq,
             H: BuildHasher,
         {
-            fn flush(&mut self) {
+            fn flush(&mut self, state: &DeserializerState) -> Result<(), Error> {
                 if let (Some(key), Some(value)) = (self.key.take(), self.value.take()) {
-                    self.map.insert(key, value);
+                    if self.map.insert(key, value).is_some() {
+                        check_duplicate_key(state)?;
+                    }
                 }
+                Ok(())
             }
         }
 
@@ -383,13 +513,19 @@ where
             H: BuildHasher + Default,
         {
             fn descriptor(&self) -> &dyn Descriptor {
-                static DESCRIPTOR: UnorderedNamedDescriptor =
-                    UnorderedNamedDescriptor { name: "map" };
+                static DESCRIPTOR: UnorderedNamedDescriptor = UnorderedNamedDescriptor {
+                    name: "map",
+                    kind: Kind::Map,
+                };
                 &DESCRIPTOR
             }
 
-            fn next_key(&mut self, _state: &DeserializerState) -> Result<SinkHandle, Error> {
-                self.flush();
+            fn map(&mut self, _state: &DeserializerState) -> Result<(), Error> {
+                Ok(())
+            }
+
+            fn next_key(&mut self, state: &DeserializerState) -> Result<SinkHandle, Error> {
+                self.flush(state)?;
                 Ok(Deserialize::deserialize_into(&mut self.key))
             }
 
@@ -397,8 +533,18 @@ where
                 Ok(Deserialize::deserialize_into(&mut self.value))
             }
 
-            fn finish(&mut self, _state: &DeserializerState) -> Result<(), Error> {
-                self.flush();
+            fn value_for_key(
+                &mut self,
+                key: &str,
+                state: &DeserializerState,
+            ) -> Result<Option<SinkHandle>, Error> {
+                self.flush(state)?;
+                deserialize_key(&mut self.key, key, state)?;
+                Ok(Some(Deserialize::deserialize_into(&mut self.value)))
+            }
+
+            fn finish(&mut self, state: &DeserializerState) -> Result<(), Error> {
+                self.flush(state)?;
                 *self.slot = Some(take(&mut self.map));
                 Ok(())
             }
@@ -431,8 +577,10 @@ impl<T: Deserialize + Ord> Deserialize for BTreeSet<T> {
 
         impl<'a, T: Deserialize + Ord> Sink for BTreeSetSink<'a, T> {
             fn descriptor(&self) -> &dyn Descriptor {
-                static DESCRIPTOR: UnorderedNamedDescriptor =
-                    UnorderedNamedDescriptor { name: "BTreeSet" };
+                static DESCRIPTOR: UnorderedNamedDescriptor = UnorderedNamedDescriptor {
+                    name: "BTreeSet",
+                    kind: Kind::Seq,
+                };
                 &DESCRIPTOR
             }
 
@@ -490,8 +638,10 @@ where
             H: BuildHasher + Default,
         {
             fn descriptor(&self) -> &dyn Descriptor {
-                static DESCRIPTOR: UnorderedNamedDescriptor =
-                    UnorderedNamedDescriptor { name: "HashSet" };
+                static DESCRIPTOR: UnorderedNamedDescriptor = UnorderedNamedDescriptor {
+                    name: "HashSet",
+                    kind: Kind::Seq,
+                };
                 &DESCRIPTOR
             }
 
@@ -528,32 +678,48 @@ where
         let sink = Deserialize::deserialize_into(out.as_mut().unwrap());
         match sink {
             SinkHandle::Null(_) => sink,
-            sink => SinkHandle::boxed(NullIgnoringSink { sink }),
+            sink => SinkHandle::boxed(NullIgnoringSink {
+                sink,
+                active: false,
+            }),
         }
     }
 
-    fn __private_initial_value() -> Option<Self> {
+    fn missing_value() -> Option<Self> {
         Some(None)
     }
 }
 
+// the inner sink is only finished if it received a value.  For flattening
+// this means that at least one of its keys must have been seen.
 struct NullIgnoringSink<'a> {
     sink: SinkHandle<'a>,
+    active: bool,
 }
 
 impl<'a> Sink for NullIgnoringSink<'a> {
     fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
         match atom {
             Atom::Null => Ok(()),
-            other => self.sink.atom(other, state),
+            Atom::Str(ref value)
+                if value.is_empty() && *state.get::<Coercion>() == Coercion::Lenient =>
+            {
+                Ok(())
+            }
+            other => {
+                self.active = true;
+                self.sink.atom(other, state)
+            }
         }
     }
 
     fn map(&mut self, state: &DeserializerState) -> Result<(), Error> {
+        self.active = true;
         self.sink.map(state)
     }
 
     fn seq(&mut self, state: &DeserializerState) -> Result<(), Error> {
+        self.active = true;
         self.sink.seq(state)
     }
 
@@ -565,6 +731,26 @@ impl<'a> Sink for NullIgnoringSink<'a> {
         self.sink.next_value(state)
     }
 
+    fn value_for_key(
+        &mut self,
+        key: &str,
+        state: &DeserializerState,
+    ) -> Result<Option<SinkHandle>, Error> {
+        let rv = self.sink.value_for_key(key, state)?;
+        if rv.is_some() {
+            self.active = true;
+        }
+        Ok(rv)
+    }
+
+    fn finish(&mut self, state: &DeserializerState) -> Result<(), Error> {
+        if self.active {
+            self.sink.finish(state)
+        } else {
+            Ok(())
+        }
+    }
+
     fn descriptor(&self) -> &dyn Descriptor {
         self.sink.descriptor()
     }
@@ -587,7 +773,7 @@ macro_rules! deserialize_for_tuple {
 
                 impl<'a, $($name: Deserialize,)*> Sink for TupleSink<'a, $($name,)*> {
                     fn descriptor(&self) -> &dyn Descriptor {
-                        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "tuple" };
+                        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "tuple", kind: Kind::Seq };
                         &DESCRIPTOR
                     }
 
@@ -674,7 +860,10 @@ impl<T: Deserialize, const N: usize> Deserialize for [T; N] {
 
         impl<'a, T: Deserialize + 'a, const N: usize> Sink for ArraySink<'a, T, N> {
             fn descriptor(&self) -> &dyn Descriptor {
-                static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "array" };
+                static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+                    name: "array",
+                    kind: Kind::Seq,
+                };
                 &DESCRIPTOR
             }
 
@@ -760,60 +949,606 @@ impl<T: Deserialize, const N: usize> Deserialize for [T; N] {
     }
 }
 
-impl<T: Deserialize> Deserialize for Box<T> {
-    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
-        struct BoxSink<'a, T> {
-            out: &'a mut Option<Box<T>>,
-            sink: OwnedSink<T>,
+// deserializes into an owned sink and converts the value on finish.
+struct WrappingSink<'a, T, U> {
+    out: &'a mut Option<U>,
+    sink: OwnedSink<T>,
+    wrap: fn(T) -> U,
+}
+
+impl<'a, T: Deserialize, U> WrappingSink<'a, T, U> {
+    fn make_handle(out: &'a mut Option<U>, wrap: fn(T) -> U) -> SinkHandle<'a>
+    where
+        T: 'a,
+        U: 'a,
+    {
+        SinkHandle::boxed(WrappingSink {
+            out,
+            sink: OwnedSink::deserialize(),
+            wrap,
+        })
+    }
+}
+
+impl<'a, T: Deserialize, U> Sink for WrappingSink<'a, T, U> {
+    fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+        self.sink.borrow_mut().atom(atom, state)
+    }
+
+    fn map(&mut self, state: &DeserializerState) -> Result<(), Error> {
+        self.sink.borrow_mut().map(state)
+    }
+
+    fn seq(&mut self, state: &DeserializerState) -> Result<(), Error> {
+        self.sink.borrow_mut().seq(state)
+    }
+
+    fn next_key(&mut self, state: &DeserializerState) -> Result<SinkHandle, Error> {
+        self.sink.borrow_mut().next_key(state)
+    }
+
+    fn next_value(&mut self, state: &DeserializerState) -> Result<SinkHandle, Error> {
+        self.sink.borrow_mut().next_value(state)
+    }
+
+    fn value_for_key(
+        &mut self,
+        key: &str,
+        state: &DeserializerState,
+    ) -> Result<Option<SinkHandle>, Error> {
+        self.sink.borrow_mut().value_for_key(key, state)
+    }
+
+    fn finish(&mut self, state: &DeserializerState) -> Result<(), Error> {
+        self.sink.borrow_mut().finish(state)?;
+        *self.out = self.sink.take().map(self.wrap);
+        Ok(())
+    }
+
+    fn descriptor(&self) -> &dyn Descriptor {
+        self.sink.borrow().descriptor()
+    }
+
+    fn expecting(&self) -> Cow<'_, str> {
+        self.sink.borrow().expecting()
+    }
+}
+
+macro_rules! deserialize_for_wrapper {
+    ($ty:ident, $wrap:expr) => {
+        impl<T: Deserialize> Deserialize for $ty<T> {
+            fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+                WrappingSink::make_handle(out, $wrap)
+            }
+
+            fn missing_value() -> Option<Self> {
+                T::missing_value().map($wrap)
+            }
         }
+    };
+}
 
-        impl<'a, T: Deserialize> Sink for BoxSink<'a, T> {
-            fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
-                self.sink.borrow_mut().atom(atom, state)
+deserialize_for_wrapper!(Box, Box::new);
+deserialize_for_wrapper!(Rc, Rc::new);
+deserialize_for_wrapper!(Arc, Arc::new);
+deserialize_for_wrapper!(Cell, Cell::new);
+deserialize_for_wrapper!(RefCell, RefCell::new);
+deserialize_for_wrapper!(Mutex, Mutex::new);
+deserialize_for_wrapper!(RwLock, RwLock::new);
+
+macro_rules! deserialize_via {
+    ([$($param:tt)*] $ty:ty, $via:ty, $wrap:expr) => {
+        impl<$($param)*> Deserialize for $ty {
+            fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+                WrappingSink::make_handle(out, |value: $via| $wrap(value))
             }
+        }
+    };
+}
 
-            fn map(&mut self, state: &DeserializerState) -> Result<(), Error> {
-                self.sink.borrow_mut().map(state)
+deserialize_via!([T: Deserialize] VecDeque<T>, Vec<T>, VecDeque::from);
+deserialize_via!([T: Deserialize] LinkedList<T>, Vec<T>, LinkedList::from_iter);
+deserialize_via!([T: Deserialize + Ord] BinaryHeap<T>, Vec<T>, BinaryHeap::from);
+deserialize_via!([T: Deserialize] Box<[T]>, Vec<T>, Vec::into_boxed_slice);
+deserialize_via!([T: Deserialize] Rc<[T]>, Vec<T>, Rc::from);
+deserialize_via!([T: Deserialize] Arc<[T]>, Vec<T>, Arc::from);
+deserialize_via!(['a, T: Deserialize + Clone] Cow<'a, [T]>, Vec<T>, Cow::Owned);
+deserialize_via!([] Box<str>, String, String::into_boxed_str);
+deserialize_via!([] Rc<str>, String, Rc::from);
+deserialize_via!([] Arc<str>, String, Arc::from);
+deserialize_via!(['a] Cow<'a, str>, String, Cow::Owned);
+
+macro_rules! deserialize_for_weak {
+    ($ty:ty) => {
+        /// Weak references cannot hold a value on their own.  The value is
+        /// deserialized as optional and discarded.
+        impl<T: Deserialize> Deserialize for $ty {
+            fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+                WrappingSink::make_handle(out, |_: Option<T>| <$ty>::new())
             }
 
-            fn seq(&mut self, state: &DeserializerState) -> Result<(), Error> {
-                self.sink.borrow_mut().seq(state)
+            fn missing_value() -> Option<Self> {
+                Some(<$ty>::new())
             }
+        }
+    };
+}
 
-            fn next_key(&mut self, state: &DeserializerState) -> Result<SinkHandle, Error> {
-                self.sink.borrow_mut().next_key(state)
+deserialize_for_weak!(rc::Weak<T>);
+deserialize_for_weak!(sync::Weak<T>);
+
+macro_rules! from_str_sink {
+    ($ty:ty, $name:expr, $what:expr) => {
+        impl Sink for SlotWrapper<$ty> {
+            fn descriptor(&self) -> &dyn Descriptor {
+                static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+                    name: $name,
+                    kind: Kind::String,
+                };
+                &DESCRIPTOR
             }
 
-            fn next_value(&mut self, state: &DeserializerState) -> Result<SinkHandle, Error> {
-                self.sink.borrow_mut().next_value(state)
+            fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+                match atom {
+                    Atom::Str(value) => {
+                        **self = Some(value.parse().map_err(|err: AddrParseError| {
+                            Error::new(
+                                ErrorKind::Unexpected,
+                                format!("invalid {} '{}'", $what, value),
+                            )
+                            .with_source(err)
+                        })?);
+                        Ok(())
+                    }
+                    other => self.unexpected_atom(other, state),
+                }
             }
+        }
+        deserialize!($ty);
+    };
+}
 
-            fn value_for_key(
-                &mut self,
-                key: &str,
-                state: &DeserializerState,
-            ) -> Result<Option<SinkHandle>, Error> {
-                self.sink.borrow_mut().value_for_key(key, state)
+from_str_sink!(IpAddr, "IpAddr", "IP address");
+from_str_sink!(Ipv4Addr, "Ipv4Addr", "IPv4 address");
+from_str_sink!(Ipv6Addr, "Ipv6Addr", "IPv6 address");
+from_str_sink!(SocketAddr, "SocketAddr", "socket address");
+from_str_sink!(SocketAddrV4, "SocketAddrV4", "IPv4 socket address");
+from_str_sink!(SocketAddrV6, "SocketAddrV6", "IPv6 socket address");
+
+macro_rules! os_str_sink {
+    ($ty:ty, $name:expr) => {
+        impl Sink for SlotWrapper<$ty> {
+            fn descriptor(&self) -> &dyn Descriptor {
+                static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+                    name: $name,
+                    kind: Kind::String,
+                };
+                &DESCRIPTOR
             }
 
-            fn finish(&mut self, state: &DeserializerState) -> Result<(), Error> {
-                self.sink.borrow_mut().finish(state)?;
-                *self.out = self.sink.take().map(Box::new);
-                Ok(())
+            fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+                match atom {
+                    Atom::Str(value) => {
+                        **self = Some(<$ty>::from(value.into_owned()));
+                        Ok(())
+                    }
+                    other => self.unexpected_atom(other, state),
+                }
             }
+        }
+        deserialize!($ty);
+    };
+}
 
+os_str_sink!(PathBuf, "PathBuf");
+os_str_sink!(OsString, "OsString");
+
+// deserializes durations and timestamps from a `{secs, nanos}` style
+// struct or from a number of seconds.
+struct SecsNanosSink<'a, T> {
+    slot: &'a mut Option<T>,
+    descriptor: &'static NamedDescriptor,
+    names: [&'static str; 2],
+    from_duration: fn(Duration) -> Result<T, Error>,
+    key: Option<String>,
+    secs: Option<u64>,
+    nanos: Option<u32>,
+    is_map: bool,
+}
+
+impl<'a, T> Sink for SecsNanosSink<'a, T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        self.descriptor
+    }
+
+    fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+        let duration = match atom {
+            Atom::U64(secs) => Duration::from_secs(secs),
+            Atom::I64(secs) if secs >= 0 => Duration::from_secs(secs as u64),
+            Atom::F64(secs) => Duration::try_from_secs_f64(secs).map_err(|err| {
+                Error::new(ErrorKind::OutOfRange, "seconds out of range").with_source(err)
+            })?,
+            Atom::I64(_) => {
+                return Err(Error::new(
+                    ErrorKind::OutOfRange,
+                    "seconds must not be negative",
+                ))
+            }
+            other => return self.unexpected_atom(other, state),
+        };
+        *self.slot = Some((self.from_duration)(duration)?);
+        Ok(())
+    }
+
+    fn map(&mut self, _state: &DeserializerState) -> Result<(), Error> {
+        self.is_map = true;
+        Ok(())
+    }
+
+    fn next_key(&mut self, _state: &DeserializerState) -> Result<SinkHandle, Error> {
+        Ok(Deserialize::deserialize_into(&mut self.key))
+    }
+
+    fn next_value(&mut self, _state: &DeserializerState) -> Result<SinkHandle, Error> {
+        let key = self.key.take().unwrap_or_default();
+        Ok(if key == self.names[0] {
+            Deserialize::deserialize_into(&mut self.secs)
+        } else if key == self.names[1] {
+            Deserialize::deserialize_into(&mut self.nanos)
+        } else {
+            SinkHandle::null()
+        })
+    }
+
+    fn finish(&mut self, _state: &DeserializerState) -> Result<(), Error> {
+        if !self.is_map {
+            return Ok(());
+        }
+        let secs = self.secs.ok_or_else(|| {
+            Error::new(
+                ErrorKind::MissingField,
+                format!("Missing field '{}'", self.names[0]),
+            )
+        })?;
+        let nanos = self.nanos.ok_or_else(|| {
+            Error::new(
+                ErrorKind::MissingField,
+                format!("Missing field '{}'", self.names[1]),
+            )
+        })?;
+        if nanos >= 1_000_000_000 {
+            return Err(Error::new(
+                ErrorKind::OutOfRange,
+                "nanoseconds must be less than one second",
+            ));
+        }
+        *self.slot = Some((self.from_duration)(Duration::new(secs, nanos))?);
+        Ok(())
+    }
+}
+
+impl Deserialize for Duration {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "Duration",
+            kind: Kind::Any,
+        };
+        SinkHandle::boxed(SecsNanosSink {
+            slot: out,
+            descriptor: &DESCRIPTOR,
+            names: ["secs", "nanos"],
+            from_duration: Ok,
+            key: None,
+            secs: None,
+            nanos: None,
+            is_map: false,
+        })
+    }
+}
+
+impl Deserialize for SystemTime {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "SystemTime",
+            kind: Kind::Any,
+        };
+        SinkHandle::boxed(SecsNanosSink {
+            slot: out,
+            descriptor: &DESCRIPTOR,
+            names: ["secs_since_epoch", "nanos_since_epoch"],
+            from_duration: |duration| {
+                UNIX_EPOCH
+                    .checked_add(duration)
+                    .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "system time out of range"))
+            },
+            key: None,
+            secs: None,
+            nanos: None,
+            is_map: false,
+        })
+    }
+}
+
+macro_rules! nonzero_sink {
+    ($ty:ty, $int:ty) => {
+        impl Sink for SlotWrapper<$ty> {
             fn descriptor(&self) -> &dyn Descriptor {
-                self.sink.borrow().descriptor()
+                static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+                    name: stringify!($ty),
+                    kind: Kind::Int,
+                };
+                &DESCRIPTOR
             }
 
-            fn expecting(&self) -> std::borrow::Cow<'_, str> {
-                self.sink.borrow().expecting()
+            fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+                let value = match atom {
+                    Atom::U64(value) => <$int>::try_from(value).ok(),
+                    Atom::I64(value) => <$int>::try_from(value).ok(),
+                    Atom::Str(ref value) if coerces_str(state) => {
+                        Some(parse_str::<$int>(value, stringify!($ty), state)?)
+                    }
+                    other => return self.unexpected_atom(other, state),
+                }
+                .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "value out of range for type"))?;
+                **self =
+                    Some(<$ty>::new(value).ok_or_else(|| {
+                        Error::new(ErrorKind::OutOfRange, "value must not be zero")
+                    })?);
+                Ok(())
             }
         }
+        deserialize!($ty);
+    };
+}
 
-        SinkHandle::boxed(BoxSink {
-            out,
-            sink: OwnedSink::deserialize(),
+nonzero_sink!(NonZeroU8, u8);
+nonzero_sink!(NonZeroU16, u16);
+nonzero_sink!(NonZeroU32, u32);
+nonzero_sink!(NonZeroU64, u64);
+nonzero_sink!(NonZeroUsize, usize);
+nonzero_sink!(NonZeroI8, i8);
+nonzero_sink!(NonZeroI16, i16);
+nonzero_sink!(NonZeroI32, i32);
+nonzero_sink!(NonZeroI64, i64);
+nonzero_sink!(NonZeroIsize, isize);
+
+deserialize_for_wrapper!(Wrapping, Wrapping);
+deserialize_for_wrapper!(Saturating, Saturating);
+deserialize_for_wrapper!(Reverse, Reverse);
+
+struct RangeSink<'a, T, R> {
+    slot: &'a mut Option<R>,
+    descriptor: &'static NamedDescriptor,
+    make: fn(T, T) -> R,
+    key: Option<String>,
+    start: Option<T>,
+    end: Option<T>,
+}
+
+impl<'a, T: Deserialize, R> Sink for RangeSink<'a, T, R> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        self.descriptor
+    }
+
+    fn map(&mut self, _state: &DeserializerState) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn next_key(&mut self, _state: &DeserializerState) -> Result<SinkHandle, Error> {
+        Ok(Deserialize::deserialize_into(&mut self.key))
+    }
+
+    fn next_value(&mut self, _state: &DeserializerState) -> Result<SinkHandle, Error> {
+        Ok(match self.key.take().as_deref() {
+            Some("start") => Deserialize::deserialize_into(&mut self.start),
+            Some("end") => Deserialize::deserialize_into(&mut self.end),
+            _ => SinkHandle::null(),
+        })
+    }
+
+    fn finish(&mut self, _state: &DeserializerState) -> Result<(), Error> {
+        let start = self
+            .start
+            .take()
+            .ok_or_else(|| Error::new(ErrorKind::MissingField, "Missing field 'start'"))?;
+        let end = self
+            .end
+            .take()
+            .ok_or_else(|| Error::new(ErrorKind::MissingField, "Missing field 'end'"))?;
+        *self.slot = Some((self.make)(start, end));
+        Ok(())
+    }
+}
+
+impl<T: Deserialize> Deserialize for Range<T> {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "Range",
+            kind: Kind::Map,
+        };
+        SinkHandle::boxed(RangeSink {
+            slot: out,
+            descriptor: &DESCRIPTOR,
+            make: |start, end| start..end,
+            key: None,
+            start: None,
+            end: None,
         })
     }
 }
+
+impl<T: Deserialize> Deserialize for RangeInclusive<T> {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "RangeInclusive",
+            kind: Kind::Map,
+        };
+        SinkHandle::boxed(RangeSink {
+            slot: out,
+            descriptor: &DESCRIPTOR,
+            make: RangeInclusive::new,
+            key: None,
+            start: None,
+            end: None,
+        })
+    }
+}
+
+// deserializes an externally tagged enum with up to two newtype variants
+// (a map with a single key) and an optional unit variant (a string).
+struct VariantSink<'a, R, A, B> {
+    slot: &'a mut Option<R>,
+    descriptor: &'static NamedDescriptor,
+    names: [&'static str; 2],
+    unit: Option<&'static str>,
+    make_unit: fn() -> Option<R>,
+    wrap_first: fn(A) -> R,
+    wrap_second: fn(B) -> R,
+    key: Option<String>,
+    first: Option<A>,
+    second: Option<B>,
+    is_map: bool,
+}
+
+impl<'a, R, A: Deserialize, B: Deserialize> VariantSink<'a, R, A, B> {
+    fn expected_names(&self) -> Vec<&'static str> {
+        self.unit.into_iter().chain(self.names).collect()
+    }
+}
+
+impl<'a, R, A: Deserialize, B: Deserialize> Sink for VariantSink<'a, R, A, B> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        self.descriptor
+    }
+
+    fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+        match (atom, self.unit) {
+            (Atom::Str(ref name), Some(unit_name)) if name == unit_name => {
+                *self.slot = (self.make_unit)();
+                Ok(())
+            }
+            (Atom::Str(name), _) => Err(Error::unknown_name(
+                "variant",
+                &name,
+                &self.expected_names(),
+            )),
+            (other, _) => self.unexpected_atom(other, state),
+        }
+    }
+
+    fn map(&mut self, _state: &DeserializerState) -> Result<(), Error> {
+        self.is_map = true;
+        Ok(())
+    }
+
+    fn next_key(&mut self, _state: &DeserializerState) -> Result<SinkHandle, Error> {
+        Ok(Deserialize::deserialize_into(&mut self.key))
+    }
+
+    fn next_value(&mut self, _state: &DeserializerState) -> Result<SinkHandle, Error> {
+        let key = self.key.take().unwrap_or_default();
+        if self.first.is_some() || self.second.is_some() {
+            Err(Error::new(
+                ErrorKind::Unexpected,
+                "expected a map with a single variant",
+            ))
+        } else if key == self.names[0] {
+            Ok(Deserialize::deserialize_into(&mut self.first))
+        } else if key == self.names[1] {
+            Ok(Deserialize::deserialize_into(&mut self.second))
+        } else {
+            Err(Error::unknown_name("variant", &key, &self.expected_names()))
+        }
+    }
+
+    fn finish(&mut self, _state: &DeserializerState) -> Result<(), Error> {
+        if !self.is_map {
+            return Ok(());
+        }
+        *self.slot = Some(match (self.first.take(), self.second.take()) {
+            (Some(value), _) => (self.wrap_first)(value),
+            (_, Some(value)) => (self.wrap_second)(value),
+            (None, None) => {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "expected a map with a single variant",
+                ))
+            }
+        });
+        Ok(())
+    }
+}
+
+impl<T: Deserialize> Deserialize for Bound<T> {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "Bound",
+            kind: Kind::Any,
+        };
+        SinkHandle::boxed(VariantSink {
+            slot: out,
+            descriptor: &DESCRIPTOR,
+            names: ["Included", "Excluded"],
+            unit: Some("Unbounded"),
+            make_unit: || Some(Bound::Unbounded),
+            wrap_first: Bound::Included,
+            wrap_second: Bound::Excluded,
+            key: None,
+            first: None,
+            second: None,
+            is_map: false,
+        })
+    }
+}
+
+impl<T: Deserialize, E: Deserialize> Deserialize for Result<T, E> {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "Result",
+            kind: Kind::Map,
+        };
+        SinkHandle::boxed(VariantSink {
+            slot: out,
+            descriptor: &DESCRIPTOR,
+            names: ["Ok", "Err"],
+            unit: None,
+            make_unit: || None,
+            wrap_first: Ok,
+            wrap_second: Err,
+            key: None,
+            first: None,
+            second: None,
+            is_map: false,
+        })
+    }
+}
+
+impl<T: ?Sized> Sink for SlotWrapper<PhantomData<T>> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "PhantomData",
+            kind: Kind::Any,
+        };
+        &DESCRIPTOR
+    }
+
+    fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+        match atom {
+            Atom::Null => {
+                **self = Some(PhantomData);
+                Ok(())
+            }
+            other => self.unexpected_atom(other, state),
+        }
+    }
+}
+
+impl<T: ?Sized> Deserialize for PhantomData<T> {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        SlotWrapper::make_handle(out)
+    }
+
+    fn missing_value() -> Option<Self> {
+        Some(PhantomData)
+    }
+}
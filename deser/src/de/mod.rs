@@ -192,7 +192,7 @@
 //! on the spot" to temporarily deserialize into.  For more information see
 //! [`OwnedSink`].
 use std::borrow::Cow;
-use std::cell::{Ref, RefMut};
+use std::cell::{Cell, Ref, RefMut};
 use std::fmt;
 use std::ops::{Deref, DerefMut};
 
@@ -280,9 +280,31 @@ impl<'a> SinkHandle<'a> {
 pub struct DeserializerState<'a> {
     extensions: Extensions,
     descriptor_stack: Vec<&'a dyn Descriptor>,
+    is_key: Cell<bool>,
 }
 
 impl<'a> DeserializerState<'a> {
+    /// Returns a builder to create a state with pre-populated extensions.
+    ///
+    /// The built state can be passed to [`DeserializeDriver::with_state`].
+    ///
+    /// ```rust
+    /// use deser::de::{Coercion, DeserializeDriver, DeserializerState};
+    ///
+    /// let state = DeserializerState::builder()
+    ///     .insert(Coercion::Lenient)
+    ///     .build();
+    /// let mut out = None::<u32>;
+    /// {
+    ///     let mut driver = DeserializeDriver::with_state(&mut out, state);
+    ///     driver.emit("42").unwrap();
+    /// }
+    /// assert_eq!(out, Some(42));
+    /// ```
+    pub fn builder() -> DeserializerStateBuilder {
+        DeserializerStateBuilder::default()
+    }
+
     /// Returns an extension value.
     pub fn get<T: Default + fmt::Debug + 'static>(&self) -> Ref<'_, T> {
         self.extensions.get()
@@ -304,6 +326,110 @@ impl<'a> DeserializerState<'a> {
     pub fn top_descriptor(&self) -> Option<&dyn Descriptor> {
         self.descriptor_stack.last().copied()
     }
+
+    /// Returns `true` if the current value is a map key.
+    ///
+    /// Many formats (like JSON) only support string keys.  Sinks for numbers,
+    /// booleans and chars use this to parse their value from a string when
+    /// they are used as map keys.
+    pub fn is_key(&self) -> bool {
+        self.is_key.get()
+    }
+
+    pub(crate) fn set_key(&self, is_key: bool) -> bool {
+        self.is_key.replace(is_key)
+    }
+}
+
+/// Builds a [`DeserializerState`] with pre-populated extensions.
+///
+/// Created by [`DeserializerState::builder`].
+#[derive(Debug, Default)]
+pub struct DeserializerStateBuilder {
+    extensions: Extensions,
+}
+
+impl DeserializerStateBuilder {
+    /// Inserts an extension value, replacing a previous value of that type.
+    pub fn insert<T: fmt::Debug + 'static>(self, value: T) -> DeserializerStateBuilder {
+        self.extensions.insert(value);
+        self
+    }
+
+    /// Builds the state.
+    pub fn build(self) -> DeserializerState<'static> {
+        DeserializerState {
+            extensions: self.extensions,
+            descriptor_stack: Vec::new(),
+            is_key: Cell::new(false),
+        }
+    }
+}
+
+/// Controls how map sinks handle duplicate keys.
+///
+/// This is an extension value that can be placed in the [`DeserializerState`]
+/// to make the `HashMap` and `BTreeMap` sinks reject duplicate keys.  By
+/// default later keys overwrite earlier ones.  Derived structs always reject
+/// duplicate fields unless `#[deser(allow_duplicate_fields)]` is set.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use deser::{Event, ErrorKind};
+/// use deser::de::{DeserializeDriver, DuplicateKeys};
+///
+/// let mut out = None::<HashMap<String, u32>>;
+/// let mut driver = DeserializeDriver::new(&mut out);
+/// *driver.state().get_mut::<DuplicateKeys>() = DuplicateKeys::Reject;
+/// driver.emit(Event::MapStart).unwrap();
+/// driver.emit("id").unwrap();
+/// driver.emit(1u64).unwrap();
+/// driver.emit("id").unwrap();
+/// driver.emit(2u64).unwrap();
+/// let err = driver.emit(Event::MapEnd).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::DuplicateField);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Later keys overwrite earlier ones.
+    #[default]
+    Overwrite,
+    /// Duplicate keys fail with [`ErrorKind::DuplicateField`](crate::ErrorKind::DuplicateField).
+    Reject,
+}
+
+/// Controls whether scalar sinks accept strings.
+///
+/// This is an extension value that can be placed in the [`DeserializerState`]
+/// to support formats which only deliver strings such as environment
+/// variables, query strings or CSV.  In lenient mode the sinks for `bool`,
+/// integers, floats and `char` parse [`Atom::Str`] values and an empty
+/// string deserializes into `None` for `Option<T>`.  By default strings are
+/// only parsed when they are map keys.
+///
+/// ```rust
+/// use deser::Event;
+/// use deser::de::{Coercion, DeserializeDriver};
+///
+/// let mut out = None::<(u32, bool, Option<f64>)>;
+/// {
+///     let mut driver = DeserializeDriver::new(&mut out);
+///     *driver.state().get_mut::<Coercion>() = Coercion::Lenient;
+///     driver.emit(Event::SeqStart).unwrap();
+///     driver.emit("42").unwrap();
+///     driver.emit("true").unwrap();
+///     driver.emit("").unwrap();
+///     driver.emit(Event::SeqEnd).unwrap();
+/// }
+/// assert_eq!(out, Some((42, true, None)));
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Coercion {
+    /// Scalars must be provided as their native atoms.
+    #[default]
+    Strict,
+    /// Strings are parsed into scalars.
+    Lenient,
 }
 
 /// A trait for deserializable types.
@@ -320,16 +446,34 @@ pub trait Deserialize: Sized {
     /// sink.
     fn deserialize_into(out: &mut Option<Self>) -> SinkHandle;
 
-    /// Provides the initial value for a slot when deserializing structures.
+    /// Provides the value for a struct field that is missing entirely.
     ///
-    /// This is not used when a `#[deser(default)]` is used.  This should become
-    /// public API longer term but for now it's private as there are some unresolved
-    /// questions about how null vs missing fields in structs should be handled.
-    #[doc(hidden)]
-    fn __private_initial_value() -> Option<Self>
-    where
-        Self: Sized,
-    {
+    /// When a derived struct finishes deserializing and a field was not
+    /// encountered, this value is used instead of failing with a
+    /// [`MissingField`](crate::ErrorKind::MissingField) error.  The default
+    /// implementation returns `None` which makes the field required.  `Option<T>`
+    /// returns `Some(None)` which is why optional fields can be left out.
+    ///
+    /// This is not used if the field has a `#[deser(default)]` attribute or
+    /// is deserialized with `#[deser(with = "...")]`.
+    ///
+    /// ```rust
+    /// use deser::de::{Deserialize, SinkHandle};
+    ///
+    /// struct Tags(Vec<String>);
+    ///
+    /// impl Deserialize for Tags {
+    ///     fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+    ///         // ...
+    ///         # unimplemented!()
+    ///     }
+    ///
+    ///     fn missing_value() -> Option<Self> {
+    ///         Some(Tags(Vec::new()))
+    ///     }
+    /// }
+    /// ```
+    fn missing_value() -> Option<Self> {
         None
     }
 
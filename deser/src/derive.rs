@@ -48,6 +48,10 @@
 //!   specific name style.  The possible values are `"lowercase"`, `"UPPERCASE"`,
 //!   `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`,
 //!   `"kebab-case"`, and `"SCREAMING-KEBAB-CASE"`.
+//! * `#[deser(rename(serialize = "...", deserialize = "..."))]`: like `rename` but with separate names
+//!   for serialization and deserialization.  Either of the two can be omitted.
+//! * `#[deser(rename_all(serialize = "...", deserialize = "..."))]`: like `rename_all` but with separate
+//!   name styles for serialization and deserialization.
 //! * `#[deser(default)]`: Instructs the deserializer to fill in all missing fields from [`Default`].
 //!   Default will be lazily invoked if any of the fields is not filled in.
 //! * `#[deser(default = "...")]`: like `default` but fills in from a function with the given name instead.
@@ -55,6 +59,43 @@
 //!   skip over all optional values that are currently not set.  This uses the
 //!   [`is_optional`](crate::ser::Serialize::is_optional) serialize method to figure out if a
 //!   a field is optional.  At the moment only `None` and `()` are considered optional.
+//! * `#[deser(allow_duplicate_fields)]`: by default a struct fails to deserialize with a
+//!   [`DuplicateField`](crate::ErrorKind::DuplicateField) error if a field (or one of its aliases)
+//!   is encountered more than once.  With this attribute later values overwrite earlier ones instead.
+//! * `#[deser(deny_unknown_fields)]`: fails deserialization if a key is encountered that does not
+//!   match any field (or flattened field).  The error lists the expected field names or suggests
+//!   the closest match.  By default unknown fields are silently ignored, so structs without this
+//!   attribute never report "did you mean" suggestions.  Unknown enum variants are always errors
+//!   (unless there is an `other` variant) and get suggestions without opting in.
+//! * `#[deser(match_keys = "...")]`: changes how keys are matched against field names and aliases
+//!   on deserialization.  The default is `"exact"`.  `"case_insensitive"` compares keys after case
+//!   folding and `"normalized"` additionally ignores `_` and `-` so that `ACCOUNT-HOLDER` matches
+//!   `account_holder`.  Fields that become ambiguous under the chosen mode are a compile error.
+//! * `#[deser(validate = "...")]`: after deserialization invokes the function with the given name
+//!   with a reference to the struct.  The function returns `Result<(), deser::Error>`.
+//! * `#[deser(remote = "...")]`: derives for a struct from another crate.  The struct with this
+//!   attribute is a mirror definition which must list the same fields with the same types as the
+//!   remote struct.  Instead of implementing the traits for the mirror, the derives generate a
+//!   `serialize_handle` and `deserialize_into` function on it which can be used with the `with`
+//!   field attribute.
+//! * `#[deser(as_seq)]`: represents the struct as a sequence of its field values in declaration order
+//!   instead of a map.  Deserialization only accepts sequences with exactly as many elements as there
+//!   are fields.  Field attributes and container attributes which only apply to maps (such as
+//!   `rename_all`, `default` or `deny_unknown_fields`) are not supported with this representation.
+//!   The struct must have between 1 and 12 fields.
+//! * `#[deser(as_string)]`: represents the struct as a string.  Serialization uses the
+//!   [`Display`](std::fmt::Display) implementation, deserialization parses the string with
+//!   [`FromStr`](std::str::FromStr).  The parse error must implement
+//!   [`std::error::Error`] and becomes the source of the returned error.  The
+//!   generated impls require `Self: Display` and `Self: FromStr` respectively, which also works for
+//!   generic types.  Container attributes which only apply to maps are not supported.
+//! * `#[deser(crate = "...")]`: sets the path to the `deser` crate used by the generated code.  This
+//!   is useful if `deser` is re-exported from another crate (eg: `"my_crate::deser"`).
+//! * `#[deser(bound = "...")]`: replaces the inferred `T: Serialize` / `T: Deserialize` bounds on the
+//!   generated impls with the given where clause predicates (eg: `"T::Value: Serialize"`).  An empty
+//!   string removes all inferred bounds.
+//! * `#[deser(bound(serialize = "...", deserialize = "..."))]`: like `bound` but with separate where
+//!   clauses for the serialize and deserialize impls.
 //!
 //! ## Enum Attributes
 //!
@@ -62,29 +103,82 @@
 //!   specific name style.  The possible values are `"lowercase"`, `"UPPERCASE"`,
 //!   `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`,
 //!   `"kebab-case"`, and `"SCREAMING-KEBAB-CASE"`.
+//! * `#[deser(rename_all(serialize = "...", deserialize = "..."))]`: like `rename_all` but with separate
+//!   name styles for serialization and deserialization.
+//! * `#[deser(repr = "...")]`: changes how the enum is represented.  The default is `"name"` which
+//!   uses the variant names.  `"int"` uses the integer discriminants (explicit `= N` discriminants
+//!   are honored).  To ease migrations `"int_or_name"` serializes the discriminant but accepts both
+//!   forms on deserialization, whereas `"name_or_int"` serializes the name but accepts both.
+//! * `#[deser(as_string)]`: like on structs represents the enum via [`Display`](std::fmt::Display)
+//!   and [`FromStr`](std::str::FromStr).
+//! * `#[deser(crate = "...")]`: sets the path to the `deser` crate used by the generated code.
 //!
 //! ## Struct Field Attributes
 //!
 //! The following attributes can be added to fields:
 //!
 //! * `#[deser(rename = "...")]`: renames the field.
+//! * `#[deser(rename(serialize = "...", deserialize = "..."))]`: renames the field separately for
+//!   serialization and deserialization.
 //! * `#[deser(default)]`: fills in the field default value from [`Default`].
 //! * `#[deser(default = "...")]`: like `default` but fills in from a function with the given name instead.
 //! * `#[deser(skip_serializing_if = "...")]`: invokes the provided callback with the value to check
 //!   if it should be skipped during serialization.
 //! * `#[deser(alias = "...")]`: provides an alias for the field name for deserialization.  This is ignored
 //!   for serialization.
+//! * `#[deser(groups = "...")]`: assigns the field to a comma separated list of groups.  The field is
+//!   only serialized if one of its groups is active in the [`Groups`](crate::ser::Groups) extension
+//!   of the serializer state.  This is ignored for deserialization.
+//! * `#[deser(with = "...")]`: serializes and deserializes the field with the functions of the given
+//!   module or type instead of the field's own implementations.  `serialize_handle` is invoked with
+//!   a reference to the value and returns a [`SerializeHandle`](crate::ser::SerializeHandle),
+//!   `deserialize_into` has the signature of [`Deserialize::deserialize_into`](crate::Deserialize::deserialize_into).
+//! * `#[deser(with_string)]`: serializes and deserializes the field as string via its
+//!   [`Display`](std::fmt::Display) and [`FromStr`](std::str::FromStr) implementations.
+//! * `#[deser(validate = "...")]`: after deserialization invokes the function with the given name
+//!   with a reference to the field value.  The function returns `Result<(), deser::Error>`.
+//! * `#[deser(range(min = ..., max = ...))]`: validates that the value is within the given bounds.
+//!   Either bound can be omitted.  Only supported on primitive integer and float fields.  `NaN`
+//!   is always rejected.
+//! * `#[deser(length(min = ..., max = ...))]`: validates that the length of the value (as
+//!   returned by its `len` method) is within the given bounds.  Either bound can be omitted.
+//! * `#[deser(non_empty)]`: validates that the value is not empty (as returned by its `is_empty`
+//!   method).
 //! * `#[deser(flatten)]`: when added to a nested struct field causes that field to be flattened into the
 //!   parent struct.  Note that flattening only works with structs (more specifically with string) keys.
-//!   This feature is enabled by [`value_for_key`](crate::de::Sink::value_for_key).
+//!   This feature is enabled by [`value_for_key`](crate::de::Sink::value_for_key).  Flattening into a
+//!   `HashMap` or `BTreeMap` collects all keys not claimed by other fields and emits them inline again
+//!   on serialization.  As such a map claims all keys, flattened `HashMap` and `BTreeMap` fields are
+//!   always tried after all other flattened fields.  Maps hidden behind type aliases or other types
+//!   are not recognized and are tried in declaration order.
+//!   Flattening an `Option<T>` sets it to `Some` only if any of its keys were seen and skips it on
+//!   serialization if it's `None`.
+//! * `#[deser(flatten(prefix = "..."))]`: like `flatten` but all keys of the flattened field carry the
+//!   given prefix.  The prefix is stripped on deserialization and added on serialization.
+//!
+//! Fields without a `default` or `with` attribute that are missing fall back to
+//! [`Deserialize::missing_value`](crate::Deserialize::missing_value).  This is why
+//! `Option<T>` fields can be left out.  If that returns `None` deserialization fails with a
+//! [`MissingField`](crate::ErrorKind::MissingField) error.  To tell missing and `null` fields
+//! apart use [`Patch<T>`](crate::Patch).
+//!
+//! Validations run once the struct was fully deserialized.  All violations (including the one of the
+//! struct level `validate` function) are collected and reported together as a single error where
+//! each field violation is prefixed with the name of the field.
 //!
 //! ## Enum Variant Attributes
 //!
 //! The following attributes can be added to enum variants:
 //!
 //! * `#[deser(rename = "...")]`: renames the enum variant.
+//! * `#[deser(rename(serialize = "...", deserialize = "..."))]`: renames the enum variant separately
+//!   for serialization and deserialization.
 //! * `#[deser(alias = "...")]`: provides an alias for the variant name for deserialization.  This is ignored
 //!   for serialization.
+//! * `#[deser(other)]`: marks the variant as catch-all for unknown variant names.  If added to a unit
+//!   variant all unknown names deserialize into it.  If added to a newtype variant (eg: `Unknown(String)`)
+//!   the unknown name is captured and emitted again unchanged on serialization.  Only one variant
+//!   can be marked as `other`.
 
 // these exist as explicit aliases only
 
@@ -1,9 +1,10 @@
 /// The default null descriptor.
 pub(crate) struct NullDescriptor;
 
-/// A primitive descriptor with just a name.
+/// A primitive descriptor with a name and kind.
 pub(crate) struct NamedDescriptor {
     pub(crate) name: &'static str,
+    pub(crate) kind: Kind,
 }
 
 /// A number descriptor provides additional information about a number type.
@@ -15,6 +16,32 @@ pub(crate) struct NumberDescriptor {
 /// A descriptor that is always unordered.
 pub(crate) struct UnorderedNamedDescriptor {
     pub(crate) name: &'static str,
+    pub(crate) kind: Kind,
+}
+
+/// The coarse kind of value a type is represented as.
+///
+/// This is returned from [`Descriptor::kind`].  On deserialization it tells
+/// formats which only have untyped scalars (such as YAML or environment
+/// variables) how a value should be interpreted, for instance if `"123"` is
+/// a string or a number.  Types which accept more than one kind of value
+/// report [`Kind::Any`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// Any value or the kind is not known.
+    Any,
+    /// A string or character.
+    String,
+    /// An integer.
+    Int,
+    /// A floating point number.
+    Float,
+    /// A boolean.
+    Bool,
+    /// A sequence.
+    Seq,
+    /// A map or struct.
+    Map,
 }
 
 /// A descriptor provides auxiliary type information.
@@ -50,6 +77,14 @@ pub trait Descriptor {
     fn unordered(&self) -> bool {
         false
     }
+
+    /// Returns the coarse kind of the value.
+    ///
+    /// For instance a `u32` returns [`Kind::Int`] here, a `Vec` returns
+    /// [`Kind::Seq`].  The default implementation returns [`Kind::Any`].
+    fn kind(&self) -> Kind {
+        Kind::Any
+    }
 }
 
 impl Descriptor for NullDescriptor {}
@@ -58,6 +93,10 @@ impl Descriptor for NamedDescriptor {
     fn name(&self) -> Option<&str> {
         Some(self.name)
     }
+
+    fn kind(&self) -> Kind {
+        self.kind
+    }
 }
 
 impl Descriptor for NumberDescriptor {
@@ -72,6 +111,13 @@ impl Descriptor for NumberDescriptor {
             None
         }
     }
+
+    fn kind(&self) -> Kind {
+        match self.name {
+            "f32" | "f64" => Kind::Float,
+            _ => Kind::Int,
+        }
+    }
 }
 
 impl Descriptor for UnorderedNamedDescriptor {
@@ -82,4 +128,8 @@ impl Descriptor for UnorderedNamedDescriptor {
     fn unordered(&self) -> bool {
         true
     }
+
+    fn kind(&self) -> Kind {
+        self.kind
+    }
 }
@@ -8,6 +8,7 @@ pub enum ErrorKind {
     UnsupportedType,
     Unexpected,
     MissingField,
+    DuplicateField,
     OutOfRange,
     WrongLength,
     EndOfFile,
@@ -41,6 +42,74 @@ impl Error {
     pub fn kind(&self) -> ErrorKind {
         self.kind
     }
+
+    pub(crate) fn with_field_context(self, name: &str) -> Error {
+        Error {
+            kind: self.kind,
+            msg: format!("invalid field '{}': {}", name, self.msg).into(),
+            source: self.source,
+        }
+    }
+
+    pub(crate) fn unknown_name(what: &str, name: &str, expected: &[&str]) -> Error {
+        let msg = if let Some(suggestion) = find_suggestion(name, expected) {
+            format!(
+                "unknown {} {:?}, did you mean {:?}?",
+                what, name, suggestion
+            )
+        } else if expected.is_empty() {
+            format!("unknown {} {:?}, there are no {}s", what, name, what)
+        } else {
+            let expected = expected
+                .iter()
+                .map(|x| format!("{:?}", x))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("unknown {} {:?}, expected one of {}", what, name, expected)
+        };
+        Error::new(ErrorKind::Unexpected, msg)
+    }
+
+    pub(crate) fn combine(mut errors: Vec<Error>) -> Option<Error> {
+        if errors.len() <= 1 {
+            return errors.pop();
+        }
+        let msg = errors
+            .iter()
+            .map(|err| &*err.msg)
+            .collect::<Vec<_>>()
+            .join("; ");
+        let first = errors.swap_remove(0);
+        Some(Error {
+            kind: first.kind,
+            msg: msg.into(),
+            source: first.source,
+        })
+    }
+}
+
+fn find_suggestion<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), *candidate))
+        .filter(|(distance, candidate)| *distance <= candidate.chars().count() / 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let cost = if a_char == *b_char { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(prev + 1);
+        }
+    }
+    row[b.len()]
 }
 
 impl fmt::Display for Error {
@@ -47,12 +47,16 @@ pub mod de;
 mod error;
 pub mod ser;
 
+mod bytes;
 mod descriptors;
 mod extensions;
+mod patch;
 
-pub use self::descriptors::Descriptor;
+pub use self::bytes::{ByteBuf, Bytes};
+pub use self::descriptors::{Descriptor, Kind};
 pub use self::error::{Error, ErrorKind};
 pub use self::event::{Atom, Event};
+pub use self::patch::Patch;
 
 // common re-exports
 
@@ -74,19 +78,269 @@ pub mod derive;
 pub mod __derive {
     pub use std::borrow::Cow;
     pub use std::boxed::Box;
+    pub use std::convert::Into;
     pub use std::default::Default;
+    pub use std::error::Error as StdError;
+    pub use std::fmt::Display;
+    pub use std::format;
+    pub use std::marker::{Send, Sync};
+    pub use std::mem::transmute;
     pub use std::option::Option::{self, None, Some};
     pub use std::result::Result::{Err, Ok};
+    pub use std::str::FromStr;
+    pub use std::string::{String, ToString};
+    pub use std::vec::Vec;
     pub type Result<T> = std::result::Result<T, super::Error>;
     pub type StrCow<'a> = Cow<'a, str>;
 
-    pub fn new_missing_field_error(name: &str) -> super::Error {
+    pub fn new_missing_field_error(name: &str, expected: &[&str]) -> super::Error {
+        let expected = expected
+            .iter()
+            .map(|x| format!("{:?}", x))
+            .collect::<Vec<_>>()
+            .join(", ");
         super::Error::new(
             super::ErrorKind::MissingField,
-            format!("Missing field '{}'", name),
+            format!("Missing field '{}', expected fields are {}", name, expected),
         )
     }
 
+    pub fn case_fold_key(key: &str) -> String {
+        key.to_lowercase()
+    }
+
+    pub fn normalize_key(key: &str) -> String {
+        key.chars()
+            .filter(|&c| c != '_' && c != '-')
+            .collect::<String>()
+            .to_lowercase()
+    }
+
+    pub fn new_unknown_field_error(name: &str, expected: &[&str]) -> super::Error {
+        super::Error::unknown_name("field", name, expected)
+    }
+
+    pub fn new_unknown_variant_error(name: &str, expected: &[&str]) -> super::Error {
+        super::Error::unknown_name("variant", name, expected)
+    }
+
+    pub fn new_duplicate_field_error(name: &str) -> super::Error {
+        super::Error::new(
+            super::ErrorKind::DuplicateField,
+            format!("Duplicate field '{}'", name),
+        )
+    }
+
+    fn describe_bounds<T: std::fmt::Display>(min: &Option<T>, max: &Option<T>) -> String {
+        match (min, max) {
+            (Some(min), Some(max)) => format!("between {} and {}", min, max),
+            (Some(min), None) => format!("at least {}", min),
+            (None, Some(max)) => format!("at most {}", max),
+            (None, None) => "anything".into(),
+        }
+    }
+
+    pub fn validate_range<T: PartialOrd + std::fmt::Display>(
+        value: &T,
+        min: Option<T>,
+        max: Option<T>,
+    ) -> Result<()> {
+        // NaN is not comparable to itself and never within bounds
+        if value.partial_cmp(value).is_none()
+            || min.as_ref().is_some_and(|min| value < min)
+            || max.as_ref().is_some_and(|max| value > max)
+        {
+            Err(super::Error::new(
+                super::ErrorKind::OutOfRange,
+                format!(
+                    "value must be {}, got {}",
+                    describe_bounds(&min, &max),
+                    value
+                ),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn validate_length(len: usize, min: Option<usize>, max: Option<usize>) -> Result<()> {
+        if min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) {
+            Err(super::Error::new(
+                super::ErrorKind::WrongLength,
+                format!(
+                    "length must be {}, got {}",
+                    describe_bounds(&min, &max),
+                    len
+                ),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn validate_non_empty(is_empty: bool) -> Result<()> {
+        if is_empty {
+            Err(super::Error::new(
+                super::ErrorKind::WrongLength,
+                "value must not be empty",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn collect_field_error(errors: &mut Vec<super::Error>, name: &str, result: Result<()>) {
+        if let Err(err) = result {
+            errors.push(err.with_field_context(name));
+        }
+    }
+
+    pub fn collect_error(errors: &mut Vec<super::Error>, result: Result<()>) {
+        if let Err(err) = result {
+            errors.push(err);
+        }
+    }
+
+    pub fn finish_validation(errors: Vec<super::Error>) -> Result<()> {
+        match super::Error::combine(errors) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    pub fn discriminant_from_atom(atom: &super::Atom) -> Option<i128> {
+        match *atom {
+            super::Atom::U64(value) => Some(value as i128),
+            super::Atom::I64(value) => Some(value as i128),
+            _ => None,
+        }
+    }
+
+    pub fn discriminant_to_atom(value: i128) -> super::Atom<'static> {
+        if value < 0 {
+            super::Atom::I64(value as i64)
+        } else {
+            super::Atom::U64(value as u64)
+        }
+    }
+
+    pub fn map_as_struct_emitter<'a>(
+        emitter: Box<dyn super::ser::MapEmitter + 'a>,
+    ) -> Box<dyn super::ser::StructEmitter + 'a> {
+        struct MapStructEmitter<'a>(Box<dyn super::ser::MapEmitter + 'a>);
+
+        impl<'a> super::ser::StructEmitter for MapStructEmitter<'a> {
+            fn next(
+                &mut self,
+                state: &super::ser::SerializerState,
+            ) -> Result<Option<(StrCow<'_>, super::ser::SerializeHandle)>> {
+                let key_handle = match self.0.next_key(state)? {
+                    Some(key_handle) => key_handle,
+                    None => return Ok(None),
+                };
+                let key = match key_handle.serialize(state)? {
+                    super::ser::Chunk::Atom(super::Atom::Str(key)) => key.into_owned(),
+                    super::ser::Chunk::Atom(super::Atom::Char(key)) => key.to_string(),
+                    _ => {
+                        return Err(super::Error::new(
+                            super::ErrorKind::Unexpected,
+                            "flattened map keys must be strings",
+                        ))
+                    }
+                };
+                key_handle.finish(state)?;
+                drop(key_handle);
+                Ok(Some((Cow::Owned(key), self.0.next_value(state)?)))
+            }
+        }
+
+        Box::new(MapStructEmitter(emitter))
+    }
+
+    pub mod as_string {
+        use std::borrow::Cow;
+        use std::fmt::Display;
+        use std::str::FromStr;
+
+        use crate::de::{DeserializerState, Sink, SinkHandle};
+        use crate::descriptors::{Descriptor, Kind, NamedDescriptor};
+        use crate::ser::{Chunk, Serialize, SerializeHandle, SerializerState};
+        use crate::{Atom, Error, ErrorKind};
+
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "string",
+            kind: Kind::String,
+        };
+
+        struct DisplaySerialize<'a, T>(&'a T);
+
+        impl<'a, T: Display> Serialize for DisplaySerialize<'a, T> {
+            fn descriptor(&self) -> &dyn Descriptor {
+                &DESCRIPTOR
+            }
+
+            fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+                Ok(Chunk::Atom(Atom::Str(Cow::Owned(self.0.to_string()))))
+            }
+        }
+
+        struct FromStrSink<'a, T> {
+            out: &'a mut Option<T>,
+            descriptor: NamedDescriptor,
+        }
+
+        impl<'a, T> Sink for FromStrSink<'a, T>
+        where
+            T: FromStr,
+            T::Err: std::error::Error + Send + Sync + 'static,
+        {
+            fn descriptor(&self) -> &dyn Descriptor {
+                &self.descriptor
+            }
+
+            fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+                match atom {
+                    Atom::Str(value) => {
+                        *self.out = Some(value.parse().map_err(|err: T::Err| {
+                            Error::new(ErrorKind::Unexpected, "invalid value").with_source(err)
+                        })?);
+                        Ok(())
+                    }
+                    other => self.unexpected_atom(other, state),
+                }
+            }
+        }
+
+        pub fn serialize_handle<T: Display>(value: &T) -> SerializeHandle<'_> {
+            SerializeHandle::boxed(DisplaySerialize(value))
+        }
+
+        pub fn deserialize_into<T>(out: &mut Option<T>) -> SinkHandle<'_>
+        where
+            T: FromStr,
+            T::Err: std::error::Error + Send + Sync + 'static,
+        {
+            deserialize_into_named(out, "string")
+        }
+
+        pub fn deserialize_into_named<'a, T>(
+            out: &'a mut Option<T>,
+            name: &'static str,
+        ) -> SinkHandle<'a>
+        where
+            T: FromStr,
+            T::Err: std::error::Error + Send + Sync + 'static,
+        {
+            SinkHandle::boxed(FromStrSink {
+                out,
+                descriptor: NamedDescriptor {
+                    name,
+                    kind: Kind::String,
+                },
+            })
+        }
+    }
+
     mod _hack {
         pub type Str = str;
     }
@@ -0,0 +1,205 @@
+use crate::de::{Deserialize, DeserializerState, OwnedSink, Sink, SinkHandle};
+use crate::descriptors::Descriptor;
+use crate::error::Error;
+use crate::event::Atom;
+use crate::ser::{Chunk, Serialize, SerializerState};
+
+/// A value that distinguishes between absent, null and set.
+///
+/// This is useful for partial updates where a missing field means "leave
+/// unchanged" and an explicit `null` means "clear".  When used as a field in a
+/// derived struct a missing field deserializes into [`Patch::Absent`] via
+/// [`Deserialize::missing_value`].  On serialization only [`Patch::Absent`]
+/// is considered optional so that `#[deser(skip_serializing_optionals)]`
+/// leaves out absent fields but emits explicit nulls.
+///
+/// ```rust
+/// use deser::{Deserialize, Serialize, Patch};
+///
+/// #[derive(Deserialize, Serialize)]
+/// #[deser(skip_serializing_optionals)]
+/// struct UserPatch {
+///     name: Patch<String>,
+///     email: Patch<String>,
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Patch<T> {
+    /// The value was not provided.
+    #[default]
+    Absent,
+    /// The value was explicitly set to null.
+    Null,
+    /// The value was set.
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    /// Returns `true` if the value was not provided.
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Patch::Absent)
+    }
+
+    /// Returns `true` if the value was explicitly set to null.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Patch::Null)
+    }
+
+    /// Returns `true` if a value was set.
+    pub fn is_value(&self) -> bool {
+        matches!(self, Patch::Value(_))
+    }
+
+    /// Converts from `&Patch<T>` to `Patch<&T>`.
+    pub fn as_ref(&self) -> Patch<&T> {
+        match self {
+            Patch::Absent => Patch::Absent,
+            Patch::Null => Patch::Null,
+            Patch::Value(value) => Patch::Value(value),
+        }
+    }
+
+    /// Returns the value if one was set.
+    pub fn value(self) -> Option<T> {
+        match self {
+            Patch::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Converts the patch into a nested option.
+    ///
+    /// [`Patch::Absent`] becomes `None` and [`Patch::Null`] becomes `Some(None)`.
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            Patch::Absent => None,
+            Patch::Null => Some(None),
+            Patch::Value(value) => Some(Some(value)),
+        }
+    }
+
+    /// Applies the patch to an optional value.
+    ///
+    /// An absent patch leaves the target unchanged, null clears it.
+    pub fn apply_to(self, target: &mut Option<T>) {
+        match self {
+            Patch::Absent => {}
+            Patch::Null => *target = None,
+            Patch::Value(value) => *target = Some(value),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Patch<T> {
+    fn from(value: Option<T>) -> Patch<T> {
+        match value {
+            Some(value) => Patch::Value(value),
+            None => Patch::Null,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        match self {
+            Patch::Value(value) => value.descriptor(),
+            _ => &PatchDescriptor,
+        }
+    }
+
+    fn is_optional(&self) -> bool {
+        self.is_absent()
+    }
+
+    fn serialize(&self, state: &SerializerState) -> Result<Chunk, Error> {
+        match self {
+            Patch::Value(value) => value.serialize(state),
+            Patch::Absent | Patch::Null => Ok(Chunk::Atom(Atom::Null)),
+        }
+    }
+
+    fn finish(&self, state: &SerializerState) -> Result<(), Error> {
+        match self {
+            Patch::Value(value) => value.finish(state),
+            Patch::Absent | Patch::Null => Ok(()),
+        }
+    }
+}
+
+impl<T: Deserialize> Deserialize for Patch<T> {
+    fn deserialize_into(out: &mut Option<Self>) -> SinkHandle {
+        SinkHandle::boxed(PatchSink {
+            out,
+            sink: OwnedSink::deserialize(),
+            null: false,
+        })
+    }
+
+    fn missing_value() -> Option<Self> {
+        Some(Patch::Absent)
+    }
+}
+
+struct PatchDescriptor;
+
+impl Descriptor for PatchDescriptor {
+    fn name(&self) -> Option<&str> {
+        Some("patch")
+    }
+}
+
+struct PatchSink<'a, T> {
+    out: &'a mut Option<Patch<T>>,
+    sink: OwnedSink<T>,
+    null: bool,
+}
+
+impl<'a, T: Deserialize> Sink for PatchSink<'a, T> {
+    fn atom(&mut self, atom: Atom, state: &DeserializerState) -> Result<(), Error> {
+        match atom {
+            Atom::Null => {
+                self.null = true;
+                Ok(())
+            }
+            other => self.sink.borrow_mut().atom(other, state),
+        }
+    }
+
+    fn map(&mut self, state: &DeserializerState) -> Result<(), Error> {
+        self.sink.borrow_mut().map(state)
+    }
+
+    fn seq(&mut self, state: &DeserializerState) -> Result<(), Error> {
+        self.sink.borrow_mut().seq(state)
+    }
+
+    fn next_key(&mut self, state: &DeserializerState) -> Result<SinkHandle, Error> {
+        self.sink.borrow_mut().next_key(state)
+    }
+
+    fn next_value(&mut self, state: &DeserializerState) -> Result<SinkHandle, Error> {
+        self.sink.borrow_mut().next_value(state)
+    }
+
+    fn value_for_key(
+        &mut self,
+        key: &str,
+        state: &DeserializerState,
+    ) -> Result<Option<SinkHandle>, Error> {
+        self.sink.borrow_mut().value_for_key(key, state)
+    }
+
+    fn finish(&mut self, state: &DeserializerState) -> Result<(), Error> {
+        if self.null {
+            *self.out = Some(Patch::Null);
+        } else {
+            self.sink.borrow_mut().finish(state)?;
+            *self.out = self.sink.take().map(Patch::Value);
+        }
+        Ok(())
+    }
+
+    fn descriptor(&self) -> &dyn Descriptor {
+        self.sink.borrow().descriptor()
+    }
+}
@@ -3,7 +3,6 @@ use std::mem::ManuallyDrop;
 use std::ops::Deref;
 
 use crate::error::Error;
-use crate::extensions::Extensions;
 use crate::ser::{Chunk, SerializerState};
 use crate::{Descriptor, Event, Serialize};
 
@@ -15,7 +14,7 @@ use super::{MapEmitter, SeqEmitter, SerializeHandle, StructEmitter};
 /// stream.  As a user one has to call [`next`](Self::next) until `None`
 /// is returned, indicating the end of the event stream.
 pub struct SerializeDriver<'a> {
-    state: SerializerState<'static>,
+    state: SerializerState<'a>,
     state_stack: Vec<DriverState>,
     serializable_stack: ManuallyDrop<Vec<SerializableOnStack>>,
     emitter_stack: ManuallyDrop<Vec<Emitter>>,
@@ -77,13 +76,22 @@ const STACK_CAPACITY: usize = 128;
 impl<'a> SerializeDriver<'a> {
     /// Creates a new driver which serializes the given value implementing [`Serialize`].
     pub fn new(serializable: &'a dyn Serialize) -> SerializeDriver<'a> {
+        SerializeDriver::with_state(serializable, SerializerState::builder().build())
+    }
+
+    /// Creates a new driver with a pre-populated state.
+    ///
+    /// See [`SerializerState::builder`] for how to create the state.
+    pub fn with_state(
+        serializable: &'a dyn Serialize,
+        mut state: SerializerState<'a>,
+    ) -> SerializeDriver<'a> {
         let serializable =
             unsafe { extend_lifetime!(SerializeHandle::Borrowed(serializable), SerializeHandle) };
+        state.descriptor_stack.clear();
+        state.descriptor_stack.reserve(STACK_CAPACITY);
         SerializeDriver {
-            state: SerializerState {
-                extensions: Extensions::default(),
-                descriptor_stack: Vec::with_capacity(STACK_CAPACITY),
-            },
+            state,
             emitter_stack: ManuallyDrop::new(Vec::with_capacity(STACK_CAPACITY)),
             serializable_stack: ManuallyDrop::new({
                 let mut vec = Vec::with_capacity(STACK_CAPACITY);
@@ -202,18 +210,18 @@ impl<'a> SerializeDriver<'a> {
                 }
                 DriverState::Serialize => {
                     let serializable = self.serializable_stack.last().unwrap();
+                    // the descriptor is requested first as wrappers such as
+                    // `Mutex` can only inspect their value before it is locked
+                    // for serialization.
+                    let descriptor =
+                        unsafe { extend_lifetime!(serializable.descriptor(), &dyn Descriptor) };
                     match unsafe { extend_lifetime!(serializable.serialize(&self.state)?, Chunk) } {
                         Chunk::Atom(atom) => {
-                            self.next_event = Some((Event::Atom(atom), unsafe {
-                                extend_lifetime!(serializable.descriptor(), &dyn Descriptor)
-                            }));
+                            self.next_event = Some((Event::Atom(atom), descriptor));
                             *state = DriverState::FinishSerialize;
                             return Ok(());
                         }
                         Chunk::Struct(emitter) => {
-                            let descriptor = unsafe {
-                                extend_lifetime!(serializable.descriptor(), &dyn Descriptor)
-                            };
                             self.next_event = Some((Event::MapStart, descriptor));
                             self.emitter_stack.push(Emitter::Struct(emitter));
                             *state = DriverState::StructEmitterAdvance;
@@ -221,9 +229,6 @@ impl<'a> SerializeDriver<'a> {
                             return Ok(());
                         }
                         Chunk::Map(emitter) => {
-                            let descriptor = unsafe {
-                                extend_lifetime!(serializable.descriptor(), &dyn Descriptor)
-                            };
                             self.next_event = Some((Event::MapStart, descriptor));
                             self.emitter_stack.push(Emitter::Map(emitter));
                             *state = DriverState::MapEmitterNextKey;
@@ -231,9 +236,6 @@ impl<'a> SerializeDriver<'a> {
                             return Ok(());
                         }
                         Chunk::Seq(emitter) => {
-                            let descriptor = unsafe {
-                                extend_lifetime!(serializable.descriptor(), &dyn Descriptor)
-                            };
                             self.next_event = Some((Event::SeqStart, descriptor));
                             self.emitter_stack.push(Emitter::Seq(emitter));
                             *state = DriverState::SeqEmitterAdvance;
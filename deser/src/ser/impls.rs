@@ -1,15 +1,36 @@
 use std::borrow::Cow;
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::cell::{Cell, RefCell};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::ffi::{OsStr, OsString};
 use std::hash::BuildHasher;
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
+};
+use std::ops::{Bound, Deref, Range, RangeInclusive};
+use std::path::{Path, PathBuf};
+use std::rc::{self, Rc};
+use std::sync::{self, Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::descriptors::{Descriptor, NamedDescriptor, NumberDescriptor, UnorderedNamedDescriptor};
-use crate::error::Error;
+use crate::descriptors::{
+    Descriptor, Kind, NamedDescriptor, NumberDescriptor, UnorderedNamedDescriptor,
+};
+use crate::error::{Error, ErrorKind};
 use crate::event::Atom;
-use crate::ser::{Chunk, MapEmitter, SeqEmitter, Serialize, SerializeHandle, SerializerState};
+use crate::ser::{
+    Chunk, MapEmitter, SeqEmitter, Serialize, SerializeHandle, SerializerState, StructEmitter,
+};
 
 impl Serialize for bool {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "bool" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "bool",
+            kind: Kind::Bool,
+        };
         &DESCRIPTOR
     }
 
@@ -20,7 +41,10 @@ impl Serialize for bool {
 
 impl Serialize for () {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "null" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "null",
+            kind: Kind::Any,
+        };
         &DESCRIPTOR
     }
 
@@ -53,7 +77,10 @@ impl Serialize for u8 {
 
 impl Serialize for char {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "char" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "char",
+            kind: Kind::String,
+        };
         &DESCRIPTOR
     }
 
@@ -94,7 +121,10 @@ serialize_int!(f64, F64);
 
 impl Serialize for String {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "String" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "String",
+            kind: Kind::String,
+        };
         &DESCRIPTOR
     }
 
@@ -105,7 +135,10 @@ impl Serialize for String {
 
 impl<'a> Serialize for &'a str {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "str" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "str",
+            kind: Kind::String,
+        };
         &DESCRIPTOR
     }
 
@@ -116,7 +149,10 @@ impl<'a> Serialize for &'a str {
 
 impl<'a> Serialize for Cow<'a, str> {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "str" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "str",
+            kind: Kind::String,
+        };
         &DESCRIPTOR
     }
 
@@ -130,8 +166,14 @@ where
     T: Serialize,
 {
     fn descriptor(&self) -> &dyn Descriptor {
-        static SLICE_DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "Vec" };
-        static BYTES_DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "ByteVec" };
+        static SLICE_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "Vec",
+            kind: Kind::Seq,
+        };
+        static BYTES_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "ByteVec",
+            kind: Kind::Any,
+        };
         if T::__private_slice_as_bytes(self).is_some() {
             &BYTES_DESCRIPTOR
         } else {
@@ -143,7 +185,7 @@ where
         if let Some(bytes) = T::__private_slice_as_bytes(&self[..]) {
             Ok(Chunk::Atom(Atom::Bytes(bytes)))
         } else {
-            Ok(Chunk::Seq(Box::new(SliceEmitter((&self[..]).iter()))))
+            Ok(Chunk::Seq(Box::new(IterEmitter::new(self.iter()))))
         }
     }
 }
@@ -153,29 +195,165 @@ where
     T: Serialize,
 {
     fn descriptor(&self) -> &dyn Descriptor {
-        static SLICE_DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "slice" };
-        static BYTES_DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "bytes" };
-        if T::__private_slice_as_bytes(self).is_some() {
+        slice_descriptor(self)
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(serialize_slice(self))
+    }
+}
+
+fn slice_descriptor<T: Serialize>(slice: &[T]) -> &'static dyn Descriptor {
+    static SLICE_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+        name: "slice",
+        kind: Kind::Seq,
+    };
+    static BYTES_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+        name: "bytes",
+        kind: Kind::Any,
+    };
+    if T::__private_slice_as_bytes(slice).is_some() {
+        &BYTES_DESCRIPTOR
+    } else {
+        &SLICE_DESCRIPTOR
+    }
+}
+
+fn serialize_slice<T: Serialize>(slice: &[T]) -> Chunk<'_> {
+    if let Some(bytes) = T::__private_slice_as_bytes(slice) {
+        Chunk::Atom(Atom::Bytes(bytes))
+    } else {
+        Chunk::Seq(Box::new(IterEmitter::new(slice.iter())))
+    }
+}
+
+struct IterEmitter<'a, I>(I, PhantomData<&'a ()>);
+
+impl<'a, I> IterEmitter<'a, I> {
+    fn new(iter: I) -> IterEmitter<'a, I> {
+        IterEmitter(iter, PhantomData)
+    }
+}
+
+impl<'a, I, T> SeqEmitter for IterEmitter<'a, I>
+where
+    I: Iterator<Item = &'a T>,
+    T: Serialize + 'a,
+{
+    fn next(&mut self, _state: &SerializerState) -> Result<Option<SerializeHandle>, Error> {
+        Ok(self.0.next().map(SerializeHandle::to))
+    }
+}
+
+macro_rules! serialize_for_slice_pointer {
+    ([$($param:tt)*] $ty:ty) => {
+        impl<$($param)*> Serialize for $ty {
+            fn descriptor(&self) -> &dyn Descriptor {
+                slice_descriptor(self)
+            }
+
+            fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+                Ok(serialize_slice(self))
+            }
+        }
+    };
+}
+
+serialize_for_slice_pointer!([T: Serialize] Box<[T]>);
+serialize_for_slice_pointer!([T: Serialize] Rc<[T]>);
+serialize_for_slice_pointer!([T: Serialize] Arc<[T]>);
+serialize_for_slice_pointer!(['a, T: Serialize + Clone] Cow<'a, [T]>);
+
+macro_rules! serialize_for_str_pointer {
+    ($ty:ty) => {
+        impl Serialize for $ty {
+            fn descriptor(&self) -> &dyn Descriptor {
+                static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+                    name: "str",
+                    kind: Kind::String,
+                };
+                &DESCRIPTOR
+            }
+
+            fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+                Ok(Chunk::Atom(Atom::Str(Cow::Borrowed(self))))
+            }
+        }
+    };
+}
+
+serialize_for_str_pointer!(Box<str>);
+serialize_for_str_pointer!(Rc<str>);
+serialize_for_str_pointer!(Arc<str>);
+
+impl<T> Serialize for VecDeque<T>
+where
+    T: Serialize,
+{
+    fn descriptor(&self) -> &dyn Descriptor {
+        static SEQ_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "VecDeque",
+            kind: Kind::Seq,
+        };
+        static BYTES_DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "ByteVecDeque",
+            kind: Kind::Any,
+        };
+        if T::__private_slice_as_bytes(&[]).is_some() {
             &BYTES_DESCRIPTOR
         } else {
-            &SLICE_DESCRIPTOR
+            &SEQ_DESCRIPTOR
         }
     }
 
     fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
-        if let Some(bytes) = T::__private_slice_as_bytes(self) {
+        let (front, back) = self.as_slices();
+        if let Some(front_bytes) = T::__private_slice_as_bytes(front) {
+            let bytes = if back.is_empty() {
+                front_bytes
+            } else {
+                let mut bytes = front_bytes.into_owned();
+                bytes.extend_from_slice(&T::__private_slice_as_bytes(back).unwrap());
+                Cow::Owned(bytes)
+            };
             Ok(Chunk::Atom(Atom::Bytes(bytes)))
         } else {
-            Ok(Chunk::Seq(Box::new(SliceEmitter(self.iter()))))
+            Ok(Chunk::Seq(Box::new(IterEmitter::new(self.iter()))))
         }
     }
 }
 
-struct SliceEmitter<'a, T>(std::slice::Iter<'a, T>);
+impl<T> Serialize for LinkedList<T>
+where
+    T: Serialize,
+{
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "LinkedList",
+            kind: Kind::Seq,
+        };
+        &DESCRIPTOR
+    }
 
-impl<'a, T: Serialize> SeqEmitter for SliceEmitter<'a, T> {
-    fn next(&mut self, _state: &SerializerState) -> Result<Option<SerializeHandle>, Error> {
-        Ok(self.0.next().map(SerializeHandle::to))
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(Chunk::Seq(Box::new(IterEmitter::new(self.iter()))))
+    }
+}
+
+impl<T> Serialize for BinaryHeap<T>
+where
+    T: Serialize,
+{
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: UnorderedNamedDescriptor = UnorderedNamedDescriptor {
+            name: "BinaryHeap",
+            kind: Kind::Seq,
+        };
+        &DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(Chunk::Seq(Box::new(IterEmitter::new(self.iter()))))
     }
 }
 
@@ -185,7 +363,10 @@ where
     V: Serialize,
 {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "BTreeMap" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "BTreeMap",
+            kind: Kind::Map,
+        };
         &DESCRIPTOR
     }
 
@@ -223,7 +404,10 @@ where
     H: BuildHasher,
 {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: UnorderedNamedDescriptor = UnorderedNamedDescriptor { name: "HashMap" };
+        static DESCRIPTOR: UnorderedNamedDescriptor = UnorderedNamedDescriptor {
+            name: "HashMap",
+            kind: Kind::Map,
+        };
         &DESCRIPTOR
     }
 
@@ -259,7 +443,10 @@ where
     T: Serialize,
 {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "BTreeSet" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "BTreeSet",
+            kind: Kind::Seq,
+        };
         &DESCRIPTOR
     }
 
@@ -284,7 +471,10 @@ where
     T: Serialize,
 {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: UnorderedNamedDescriptor = UnorderedNamedDescriptor { name: "HashSet" };
+        static DESCRIPTOR: UnorderedNamedDescriptor = UnorderedNamedDescriptor {
+            name: "HashSet",
+            kind: Kind::Seq,
+        };
         &DESCRIPTOR
     }
 
@@ -309,7 +499,10 @@ where
     T: Serialize,
 {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "optional" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "optional",
+            kind: Kind::Any,
+        };
         &DESCRIPTOR
     }
 
@@ -330,7 +523,7 @@ macro_rules! serialize_for_tuple {
     ($($name:ident,)+) => (
         impl<$($name: Serialize),*> Serialize for ($($name,)*) {
             fn descriptor(&self) -> &dyn Descriptor {
-                static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "tuple" };
+                static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "tuple", kind: Kind::Seq };
                 &DESCRIPTOR
             }
 
@@ -378,7 +571,10 @@ serialize_for_tuple! { T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, }
 
 impl<T: Serialize, const N: usize> Serialize for [T; N] {
     fn descriptor(&self) -> &dyn Descriptor {
-        static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: "array" };
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "array",
+            kind: Kind::Seq,
+        };
         &DESCRIPTOR
     }
 
@@ -386,7 +582,7 @@ impl<T: Serialize, const N: usize> Serialize for [T; N] {
         if let Some(bytes) = T::__private_slice_as_bytes(self) {
             Ok(Chunk::Atom(Atom::Bytes(bytes)))
         } else {
-            Ok(Chunk::Seq(Box::new(SliceEmitter(self.iter()))))
+            Ok(Chunk::Seq(Box::new(IterEmitter::new(self.iter()))))
         }
     }
 }
@@ -408,3 +604,588 @@ impl<T: Serialize> Serialize for Box<T> {
         Serialize::serialize(&**self, state)
     }
 }
+
+macro_rules! serialize_for_pointer {
+    ($ty:ident) => {
+        impl<T: Serialize> Serialize for $ty<T> {
+            fn descriptor(&self) -> &dyn Descriptor {
+                Serialize::descriptor(&**self)
+            }
+
+            fn is_optional(&self) -> bool {
+                Serialize::is_optional(&**self)
+            }
+
+            fn serialize(&self, state: &SerializerState) -> Result<Chunk, Error> {
+                Serialize::serialize(&**self, state)
+            }
+
+            fn finish(&self, state: &SerializerState) -> Result<(), Error> {
+                Serialize::finish(&**self, state)
+            }
+        }
+    };
+}
+
+serialize_for_pointer!(Rc);
+serialize_for_pointer!(Arc);
+
+// Emitters returned from a value behind a guard borrow from the value.  This
+// keeps the guard alive together with the emitter.  The emitter is declared
+// first so that it is dropped before the guard.
+struct Guarded<E, G> {
+    emitter: E,
+    _guard: G,
+}
+
+impl<'a, G> StructEmitter for Guarded<Box<dyn StructEmitter + 'a>, G> {
+    fn next(
+        &mut self,
+        state: &SerializerState,
+    ) -> Result<Option<(Cow<'_, str>, SerializeHandle)>, Error> {
+        self.emitter.next(state)
+    }
+}
+
+impl<'a, G> MapEmitter for Guarded<Box<dyn MapEmitter + 'a>, G> {
+    fn next_key(&mut self, state: &SerializerState) -> Result<Option<SerializeHandle>, Error> {
+        self.emitter.next_key(state)
+    }
+
+    fn next_value(&mut self, state: &SerializerState) -> Result<SerializeHandle, Error> {
+        self.emitter.next_value(state)
+    }
+}
+
+impl<'a, G> SeqEmitter for Guarded<Box<dyn SeqEmitter + 'a>, G> {
+    fn next(&mut self, state: &SerializerState) -> Result<Option<SerializeHandle>, Error> {
+        self.emitter.next(state)
+    }
+}
+
+/// Serializes the value behind a guard.
+///
+/// The guard must deref to a value that does not live within the guard
+/// itself (eg: a lock guard or a box) as the guard is moved.
+fn serialize_guarded<'a, G, T>(guard: G, state: &SerializerState) -> Result<Chunk<'a>, Error>
+where
+    G: Deref<Target = T> + 'a,
+    T: Serialize + 'a,
+{
+    // the value is not stored in the guard, so the reference stays valid
+    // while the guard is moved for as long as the guard is held.
+    let value = unsafe { &*(&*guard as *const T) };
+    Ok(match value.serialize(state)? {
+        Chunk::Atom(atom) => Chunk::Atom(atom.to_static()),
+        Chunk::Struct(emitter) => Chunk::Struct(Box::new(Guarded {
+            emitter,
+            _guard: guard,
+        })),
+        Chunk::Map(emitter) => Chunk::Map(Box::new(Guarded {
+            emitter,
+            _guard: guard,
+        })),
+        Chunk::Seq(emitter) => Chunk::Seq(Box::new(Guarded {
+            emitter,
+            _guard: guard,
+        })),
+    })
+}
+
+/// Returns the descriptor of a wrapper which only hands out its value
+/// through a guard or a copy.
+///
+/// Unlike `Box` or `Rc` the inner descriptor cannot be returned by reference
+/// as it would outlive the guard.  The wrapper keeps its own name but
+/// reports the kind of the inner value, or [`Kind::Any`] if the value is
+/// currently not accessible.
+macro_rules! guarded_descriptor {
+    ($name:expr, $kind:expr) => {{
+        static DESCRIPTORS: [NamedDescriptor; 7] = [
+            NamedDescriptor {
+                name: $name,
+                kind: Kind::Any,
+            },
+            NamedDescriptor {
+                name: $name,
+                kind: Kind::String,
+            },
+            NamedDescriptor {
+                name: $name,
+                kind: Kind::Int,
+            },
+            NamedDescriptor {
+                name: $name,
+                kind: Kind::Float,
+            },
+            NamedDescriptor {
+                name: $name,
+                kind: Kind::Bool,
+            },
+            NamedDescriptor {
+                name: $name,
+                kind: Kind::Seq,
+            },
+            NamedDescriptor {
+                name: $name,
+                kind: Kind::Map,
+            },
+        ];
+        let kind: Option<Kind> = $kind;
+        &DESCRIPTORS[match kind.unwrap_or(Kind::Any) {
+            Kind::Any => 0,
+            Kind::String => 1,
+            Kind::Int => 2,
+            Kind::Float => 3,
+            Kind::Bool => 4,
+            Kind::Seq => 5,
+            Kind::Map => 6,
+        }]
+    }};
+}
+
+impl<T: Serialize + Copy> Serialize for Cell<T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        guarded_descriptor!("cell", Some(self.get().descriptor().kind()))
+    }
+
+    fn is_optional(&self) -> bool {
+        self.get().is_optional()
+    }
+
+    fn serialize(&self, state: &SerializerState) -> Result<Chunk, Error> {
+        serialize_guarded(Box::new(self.get()), state)
+    }
+}
+
+fn already_borrowed() -> Error {
+    Error::new(ErrorKind::Unexpected, "value is already mutably borrowed")
+}
+
+impl<T: Serialize> Serialize for RefCell<T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        guarded_descriptor!(
+            "ref_cell",
+            self.try_borrow()
+                .ok()
+                .map(|value| value.descriptor().kind())
+        )
+    }
+
+    fn is_optional(&self) -> bool {
+        self.try_borrow().is_ok_and(|value| value.is_optional())
+    }
+
+    fn serialize(&self, state: &SerializerState) -> Result<Chunk, Error> {
+        serialize_guarded(self.try_borrow().map_err(|_| already_borrowed())?, state)
+    }
+
+    fn finish(&self, state: &SerializerState) -> Result<(), Error> {
+        self.try_borrow()
+            .map_err(|_| already_borrowed())?
+            .finish(state)
+    }
+}
+
+fn poisoned() -> Error {
+    Error::new(ErrorKind::Unexpected, "lock is poisoned")
+}
+
+impl<T: Serialize> Serialize for Mutex<T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        guarded_descriptor!(
+            "mutex",
+            self.try_lock().ok().map(|value| value.descriptor().kind())
+        )
+    }
+
+    fn is_optional(&self) -> bool {
+        self.lock().is_ok_and(|value| value.is_optional())
+    }
+
+    fn serialize(&self, state: &SerializerState) -> Result<Chunk, Error> {
+        serialize_guarded(self.lock().map_err(|_| poisoned())?, state)
+    }
+
+    fn finish(&self, state: &SerializerState) -> Result<(), Error> {
+        self.lock().map_err(|_| poisoned())?.finish(state)
+    }
+}
+
+impl<T: Serialize> Serialize for RwLock<T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        guarded_descriptor!(
+            "rw_lock",
+            self.try_read().ok().map(|value| value.descriptor().kind())
+        )
+    }
+
+    fn is_optional(&self) -> bool {
+        self.read().is_ok_and(|value| value.is_optional())
+    }
+
+    fn serialize(&self, state: &SerializerState) -> Result<Chunk, Error> {
+        serialize_guarded(self.read().map_err(|_| poisoned())?, state)
+    }
+
+    fn finish(&self, state: &SerializerState) -> Result<(), Error> {
+        self.read().map_err(|_| poisoned())?.finish(state)
+    }
+}
+
+macro_rules! serialize_for_weak {
+    ($ty:ty) => {
+        impl<T: Serialize> Serialize for $ty {
+            fn descriptor(&self) -> &dyn Descriptor {
+                static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+                    name: "optional",
+                    kind: Kind::Any,
+                };
+                &DESCRIPTOR
+            }
+
+            fn is_optional(&self) -> bool {
+                self.upgrade().is_none()
+            }
+
+            fn serialize(&self, state: &SerializerState) -> Result<Chunk, Error> {
+                match self.upgrade() {
+                    Some(value) => serialize_guarded(value, state),
+                    None => Ok(Chunk::Atom(Atom::Null)),
+                }
+            }
+
+            fn finish(&self, state: &SerializerState) -> Result<(), Error> {
+                match self.upgrade() {
+                    Some(value) => value.finish(state),
+                    None => Ok(()),
+                }
+            }
+        }
+    };
+}
+
+serialize_for_weak!(rc::Weak<T>);
+serialize_for_weak!(sync::Weak<T>);
+
+// emits a `{secs, nanos}` style struct for durations and timestamps.
+struct SecsNanosEmitter {
+    names: [&'static str; 2],
+    secs: u64,
+    nanos: u32,
+    index: usize,
+}
+
+impl StructEmitter for SecsNanosEmitter {
+    fn next(
+        &mut self,
+        _state: &SerializerState,
+    ) -> Result<Option<(Cow<'_, str>, SerializeHandle)>, Error> {
+        let index = self.index;
+        self.index += 1;
+        Ok(match index {
+            0 => Some((
+                Cow::Borrowed(self.names[0]),
+                SerializeHandle::boxed(self.secs),
+            )),
+            1 => Some((
+                Cow::Borrowed(self.names[1]),
+                SerializeHandle::boxed(self.nanos),
+            )),
+            _ => None,
+        })
+    }
+}
+
+impl Serialize for Duration {
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "Duration",
+            kind: Kind::Map,
+        };
+        &DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(Chunk::Struct(Box::new(SecsNanosEmitter {
+            names: ["secs", "nanos"],
+            secs: self.as_secs(),
+            nanos: self.subsec_nanos(),
+            index: 0,
+        })))
+    }
+}
+
+impl Serialize for SystemTime {
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "SystemTime",
+            kind: Kind::Map,
+        };
+        &DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        let duration = self.duration_since(UNIX_EPOCH).map_err(|_| {
+            Error::new(
+                ErrorKind::OutOfRange,
+                "system time must not be before the unix epoch",
+            )
+        })?;
+        Ok(Chunk::Struct(Box::new(SecsNanosEmitter {
+            names: ["secs_since_epoch", "nanos_since_epoch"],
+            secs: duration.as_secs(),
+            nanos: duration.subsec_nanos(),
+            index: 0,
+        })))
+    }
+}
+
+macro_rules! serialize_as_display {
+    ($ty:ty, $name:expr) => {
+        impl Serialize for $ty {
+            fn descriptor(&self) -> &dyn Descriptor {
+                static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+                    name: $name,
+                    kind: Kind::String,
+                };
+                &DESCRIPTOR
+            }
+
+            fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+                Ok(Chunk::Atom(Atom::Str(Cow::Owned(self.to_string()))))
+            }
+        }
+    };
+}
+
+serialize_as_display!(IpAddr, "IpAddr");
+serialize_as_display!(Ipv4Addr, "Ipv4Addr");
+serialize_as_display!(Ipv6Addr, "Ipv6Addr");
+serialize_as_display!(SocketAddr, "SocketAddr");
+serialize_as_display!(SocketAddrV4, "SocketAddrV4");
+serialize_as_display!(SocketAddrV6, "SocketAddrV6");
+
+fn serialize_os_str(value: &OsStr) -> Result<Chunk<'_>, Error> {
+    match value.to_str() {
+        Some(value) => Ok(Chunk::Atom(Atom::Str(Cow::Borrowed(value)))),
+        None => Err(Error::new(
+            ErrorKind::Unexpected,
+            "path or os string contains invalid UTF-8 characters",
+        )),
+    }
+}
+
+macro_rules! serialize_for_os_str {
+    ([$($param:tt)*] $ty:ty, $name:expr) => {
+        impl<$($param)*> Serialize for $ty {
+            fn descriptor(&self) -> &dyn Descriptor {
+                static DESCRIPTOR: NamedDescriptor = NamedDescriptor { name: $name, kind: Kind::String };
+                &DESCRIPTOR
+            }
+
+            fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+                serialize_os_str(self.as_ref())
+            }
+        }
+    };
+}
+
+serialize_for_os_str!([] PathBuf, "PathBuf");
+serialize_for_os_str!(['a] &'a Path, "Path");
+serialize_for_os_str!([] OsString, "OsString");
+serialize_for_os_str!(['a] &'a OsStr, "OsStr");
+
+macro_rules! serialize_nonzero {
+    ($ty:ty, $atom:ident) => {
+        impl Serialize for $ty {
+            fn descriptor(&self) -> &dyn Descriptor {
+                static DESCRIPTOR: NumberDescriptor = NumberDescriptor {
+                    name: stringify!($ty),
+                    precision: std::mem::size_of::<$ty>() * 8,
+                };
+                &DESCRIPTOR
+            }
+
+            fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+                Ok(Chunk::Atom(Atom::$atom(self.get() as _)))
+            }
+        }
+    };
+}
+
+serialize_nonzero!(NonZeroU8, U64);
+serialize_nonzero!(NonZeroU16, U64);
+serialize_nonzero!(NonZeroU32, U64);
+serialize_nonzero!(NonZeroU64, U64);
+serialize_nonzero!(NonZeroUsize, U64);
+serialize_nonzero!(NonZeroI8, I64);
+serialize_nonzero!(NonZeroI16, I64);
+serialize_nonzero!(NonZeroI32, I64);
+serialize_nonzero!(NonZeroI64, I64);
+serialize_nonzero!(NonZeroIsize, I64);
+
+macro_rules! serialize_for_newtype {
+    ($ty:ident) => {
+        impl<T: Serialize> Serialize for $ty<T> {
+            fn descriptor(&self) -> &dyn Descriptor {
+                self.0.descriptor()
+            }
+
+            fn is_optional(&self) -> bool {
+                self.0.is_optional()
+            }
+
+            fn serialize(&self, state: &SerializerState) -> Result<Chunk, Error> {
+                self.0.serialize(state)
+            }
+
+            fn finish(&self, state: &SerializerState) -> Result<(), Error> {
+                self.0.finish(state)
+            }
+        }
+    };
+}
+
+serialize_for_newtype!(Wrapping);
+serialize_for_newtype!(Saturating);
+serialize_for_newtype!(Reverse);
+
+struct RangeEmitter<'a, T> {
+    start: &'a T,
+    end: &'a T,
+    index: usize,
+}
+
+impl<'a, T: Serialize> StructEmitter for RangeEmitter<'a, T> {
+    fn next(
+        &mut self,
+        _state: &SerializerState,
+    ) -> Result<Option<(Cow<'_, str>, SerializeHandle)>, Error> {
+        let index = self.index;
+        self.index += 1;
+        Ok(match index {
+            0 => Some((Cow::Borrowed("start"), SerializeHandle::to(self.start))),
+            1 => Some((Cow::Borrowed("end"), SerializeHandle::to(self.end))),
+            _ => None,
+        })
+    }
+}
+
+impl<T: Serialize> Serialize for Range<T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "Range",
+            kind: Kind::Map,
+        };
+        &DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(Chunk::Struct(Box::new(RangeEmitter {
+            start: &self.start,
+            end: &self.end,
+            index: 0,
+        })))
+    }
+}
+
+impl<T: Serialize> Serialize for RangeInclusive<T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "RangeInclusive",
+            kind: Kind::Map,
+        };
+        &DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(Chunk::Struct(Box::new(RangeEmitter {
+            start: self.start(),
+            end: self.end(),
+            index: 0,
+        })))
+    }
+}
+
+// emits an externally tagged variant as a map with a single key.
+struct VariantEmitter<'a> {
+    tag: &'static str,
+    value: &'a dyn Serialize,
+    done: bool,
+}
+
+impl<'a> MapEmitter for VariantEmitter<'a> {
+    fn next_key(&mut self, _state: &SerializerState) -> Result<Option<SerializeHandle>, Error> {
+        if self.done {
+            return Ok(None);
+        }
+        self.done = true;
+        Ok(Some(SerializeHandle::boxed(self.tag)))
+    }
+
+    fn next_value(&mut self, _state: &SerializerState) -> Result<SerializeHandle, Error> {
+        Ok(SerializeHandle::Borrowed(self.value))
+    }
+}
+
+fn serialize_variant<'a>(tag: &'static str, value: &'a dyn Serialize) -> Chunk<'a> {
+    Chunk::Map(Box::new(VariantEmitter {
+        tag,
+        value,
+        done: false,
+    }))
+}
+
+impl<T: Serialize> Serialize for Bound<T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "Bound",
+            kind: Kind::Any,
+        };
+        &DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(match self {
+            Bound::Included(value) => serialize_variant("Included", value),
+            Bound::Excluded(value) => serialize_variant("Excluded", value),
+            Bound::Unbounded => Chunk::Atom(Atom::Str(Cow::Borrowed("Unbounded"))),
+        })
+    }
+}
+
+impl<T: Serialize, E: Serialize> Serialize for Result<T, E> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "Result",
+            kind: Kind::Map,
+        };
+        &DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(match self {
+            Ok(value) => serialize_variant("Ok", value),
+            Err(value) => serialize_variant("Err", value),
+        })
+    }
+}
+
+impl<T: ?Sized> Serialize for PhantomData<T> {
+    fn descriptor(&self) -> &dyn Descriptor {
+        static DESCRIPTOR: NamedDescriptor = NamedDescriptor {
+            name: "PhantomData",
+            kind: Kind::Any,
+        };
+        &DESCRIPTOR
+    }
+
+    fn serialize(&self, _state: &SerializerState) -> Result<Chunk, Error> {
+        Ok(Chunk::Atom(Atom::Null))
+    }
+
+    fn is_optional(&self) -> bool {
+        true
+    }
+}
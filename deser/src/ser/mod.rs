@@ -107,6 +107,7 @@
 //! ```
 use std::borrow::Cow;
 use std::cell::{Ref, RefMut};
+use std::collections::HashSet;
 use std::fmt;
 use std::ops::Deref;
 
@@ -203,6 +204,23 @@ impl<'a> fmt::Debug for SerializerState<'a> {
 }
 
 impl<'a> SerializerState<'a> {
+    /// Returns a builder to create a state with pre-populated extensions.
+    ///
+    /// The built state can be passed to [`SerializeDriver::with_state`].
+    ///
+    /// ```rust
+    /// use deser::ser::{Groups, SerializeDriver, SerializerState};
+    ///
+    /// let state = SerializerState::builder()
+    ///     .insert(Groups::new(["public"]))
+    ///     .build();
+    /// let driver = SerializeDriver::with_state(&42u32, state);
+    /// assert!(driver.state().get::<Groups>().contains("public"));
+    /// ```
+    pub fn builder() -> SerializerStateBuilder {
+        SerializerStateBuilder::default()
+    }
+
     /// Returns an extension value.
     pub fn get<T: Default + fmt::Debug + 'static>(&self) -> Ref<'_, T> {
         self.extensions.get()
@@ -227,6 +245,94 @@ impl<'a> SerializerState<'a> {
     }
 }
 
+/// Builds a [`SerializerState`] with pre-populated extensions.
+///
+/// Created by [`SerializerState::builder`].
+#[derive(Debug, Default)]
+pub struct SerializerStateBuilder {
+    extensions: Extensions,
+}
+
+impl SerializerStateBuilder {
+    /// Inserts an extension value, replacing a previous value of that type.
+    pub fn insert<T: fmt::Debug + 'static>(self, value: T) -> SerializerStateBuilder {
+        self.extensions.insert(value);
+        self
+    }
+
+    /// Builds the state.
+    pub fn build(self) -> SerializerState<'static> {
+        SerializerState {
+            extensions: self.extensions,
+            descriptor_stack: Vec::new(),
+        }
+    }
+}
+
+/// Selects the field groups that are serialized.
+///
+/// This is an extension value that can be placed in the [`SerializerState`]
+/// to serialize only a projection of derived structs.  Fields marked with
+/// `#[deser(groups = "...")]` are only emitted if one of their groups is
+/// active.  Fields without groups are always emitted.  By default all groups
+/// are active.
+///
+/// ```rust
+/// use deser::{Serialize, Event};
+/// use deser::ser::{Groups, SerializeDriver};
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     id: u64,
+///     #[deser(groups = "admin")]
+///     email: String,
+/// }
+///
+/// let user = User { id: 42, email: "john@example.com".into() };
+/// let mut driver = SerializeDriver::new(&user);
+/// *driver.state().get_mut::<Groups>() = Groups::new(["public"]);
+/// let mut events = Vec::new();
+/// while let Some((event, _, _)) = driver.next().unwrap() {
+///     events.push(event.to_static());
+/// }
+/// assert_eq!(events, vec![Event::MapStart, "id".into(), 42u64.into(), Event::MapEnd]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Groups {
+    active: Option<HashSet<String>>,
+}
+
+impl Groups {
+    /// Creates a selection where only the given groups are active.
+    pub fn new<I, S>(groups: I) -> Groups
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Groups {
+            active: Some(groups.into_iter().map(Into::into).collect()),
+        }
+    }
+
+    /// Creates a selection where all groups are active.
+    pub fn all() -> Groups {
+        Groups::default()
+    }
+
+    /// Checks if a single group is active.
+    pub fn contains(&self, group: &str) -> bool {
+        match self.active {
+            Some(ref active) => active.contains(group),
+            None => true,
+        }
+    }
+
+    /// Checks if any of the given groups is active.
+    pub fn is_active(&self, groups: &[&str]) -> bool {
+        groups.iter().any(|group| self.contains(group))
+    }
+}
+
 /// A struct emitter.
 ///
 /// A struct emitter is a simplified version of a [`MapEmitter`] which produces struct
@@ -1,9 +1,9 @@
 use std::borrow::Cow;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::atomic::{self, AtomicUsize};
 
-use deser::de::{DeserializeDriver, Sink, SinkHandle};
-use deser::{make_slot_wrapper, Atom, Deserialize, Event};
+use deser::de::{Coercion, DeserializeDriver, DuplicateKeys, Sink, SinkHandle};
+use deser::{make_slot_wrapper, Atom, Deserialize, ErrorKind, Event, Kind};
 
 fn deserialize<T: Deserialize>(events: Vec<Event<'_>>) -> T {
     let mut out = None;
@@ -206,3 +206,450 @@ fn test_set() {
     set.insert("bar".into());
     assert_eq!(x, set);
 }
+
+#[test]
+fn test_maps() {
+    let events = || {
+        vec![
+            Event::MapStart,
+            "a".into(),
+            1u64.into(),
+            "b".into(),
+            2u64.into(),
+            "a".into(),
+            3u64.into(),
+            Event::MapEnd,
+        ]
+    };
+
+    let map: HashMap<String, u32> = deserialize(events());
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"], 3);
+    let map: BTreeMap<String, u32> = deserialize(events());
+    assert_eq!(
+        map.into_iter().collect::<Vec<_>>(),
+        vec![("a".into(), 3), ("b".into(), 2)]
+    );
+}
+
+#[test]
+fn test_maps_reject_duplicate_keys() {
+    fn check<T: Deserialize>() {
+        let mut out = None::<T>;
+        let mut driver = DeserializeDriver::new(&mut out);
+        *driver.state().get_mut::<DuplicateKeys>() = DuplicateKeys::Reject;
+        driver.emit(Event::MapStart).unwrap();
+        driver.emit("a").unwrap();
+        driver.emit(1u64).unwrap();
+        driver.emit("a").unwrap();
+        driver.emit(2u64).unwrap();
+        let err = driver.emit(Event::MapEnd).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DuplicateField);
+    }
+
+    check::<HashMap<String, u32>>();
+    check::<BTreeMap<String, u32>>();
+}
+
+#[test]
+fn test_shared_and_interior_mutability() {
+    use std::cell::{Cell, RefCell};
+    use std::rc::{self, Rc};
+    use std::sync::{Arc, Mutex, RwLock};
+
+    let events = || vec![Event::SeqStart, 1u64.into(), 2u64.into(), Event::SeqEnd];
+
+    let value: Rc<Vec<u32>> = deserialize(events());
+    assert_eq!(*value, vec![1, 2]);
+    let value: Arc<Vec<u32>> = deserialize(events());
+    assert_eq!(*value, vec![1, 2]);
+    let value: RefCell<Vec<u32>> = deserialize(events());
+    assert_eq!(*value.borrow(), vec![1, 2]);
+    let value: Mutex<Vec<u32>> = deserialize(events());
+    assert_eq!(*value.lock().unwrap(), vec![1, 2]);
+    let value: RwLock<Vec<u32>> = deserialize(events());
+    assert_eq!(*value.read().unwrap(), vec![1, 2]);
+    let value: Cell<u32> = deserialize(vec![42u64.into()]);
+    assert_eq!(value.get(), 42);
+    let value: rc::Weak<Vec<u32>> = deserialize(events());
+    assert!(value.upgrade().is_none());
+
+    let mut out = None::<Mutex<Vec<u32>>>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    assert_eq!(driver.expected().unwrap().kind(), Kind::Seq);
+}
+
+#[test]
+fn test_collections() {
+    use std::collections::{BinaryHeap, LinkedList, VecDeque};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    let events = || vec![Event::SeqStart, 2u64.into(), 1u64.into(), Event::SeqEnd];
+
+    let value: VecDeque<u32> = deserialize(events());
+    assert_eq!(value, VecDeque::from(vec![2, 1]));
+    let value: LinkedList<u32> = deserialize(events());
+    assert_eq!(value.into_iter().collect::<Vec<_>>(), vec![2, 1]);
+    let value: BinaryHeap<u32> = deserialize(events());
+    assert_eq!(value.into_sorted_vec(), vec![1, 2]);
+    let value: Box<[u32]> = deserialize(events());
+    assert_eq!(&*value, &[2, 1]);
+    let value: Rc<[u32]> = deserialize(events());
+    assert_eq!(&*value, &[2, 1]);
+    let value: Cow<'_, [u32]> = deserialize(events());
+    assert_eq!(&*value, &[2, 1]);
+
+    let value: Box<str> = deserialize(vec!["hello".into()]);
+    assert_eq!(&*value, "hello");
+    let value: Arc<str> = deserialize(vec!["hello".into()]);
+    assert_eq!(&*value, "hello");
+    let value: Cow<'static, str> = deserialize(vec!["hello".into()]);
+    assert_eq!(value, "hello");
+
+    let bytes = || vec![Event::Atom(Atom::Bytes(Cow::Borrowed(b"ab")))];
+    let value: VecDeque<u8> = deserialize(bytes());
+    assert_eq!(value, VecDeque::from(b"ab".to_vec()));
+    let value: Box<[u8]> = deserialize(bytes());
+    assert_eq!(&*value, b"ab");
+}
+
+#[test]
+fn test_time_net_path() {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let value: Duration = deserialize(vec![
+        Event::MapStart,
+        "secs".into(),
+        2u64.into(),
+        "nanos".into(),
+        500u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(value, Duration::new(2, 500));
+    let value: Duration = deserialize(vec![1.5f64.into()]);
+    assert_eq!(value, Duration::from_millis(1500));
+    let value: SystemTime = deserialize(vec![
+        Event::MapStart,
+        "secs_since_epoch".into(),
+        60u64.into(),
+        "nanos_since_epoch".into(),
+        0u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(value, UNIX_EPOCH + Duration::from_secs(60));
+
+    let value: IpAddr = deserialize(vec!["127.0.0.1".into()]);
+    assert_eq!(value, IpAddr::V4(Ipv4Addr::LOCALHOST));
+    let value: SocketAddr = deserialize(vec!["[::1]:8080".into()]);
+    assert_eq!(value.port(), 8080);
+    let value: PathBuf = deserialize(vec!["/tmp/foo".into()]);
+    assert_eq!(value, PathBuf::from("/tmp/foo"));
+
+    let mut out = None::<Ipv4Addr>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    let err = driver.emit("localhost").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unexpected: invalid IPv4 address 'localhost'"
+    );
+
+    let mut out = None::<Duration>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    driver.emit(Event::MapStart).unwrap();
+    driver.emit("secs").unwrap();
+    driver.emit(1u64).unwrap();
+    let err = driver.emit(Event::MapEnd).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingField);
+}
+
+#[test]
+fn test_small_std_types() {
+    use std::cmp::Reverse;
+    use std::marker::PhantomData;
+    use std::num::{NonZeroI8, NonZeroU32, Wrapping};
+    use std::ops::{Bound, Range, RangeInclusive};
+
+    let value: NonZeroU32 = deserialize(vec![42u64.into()]);
+    assert_eq!(value.get(), 42);
+    let value: NonZeroI8 = deserialize(vec![(-1i64).into()]);
+    assert_eq!(value.get(), -1);
+    let mut out = None::<NonZeroU32>;
+    let err = DeserializeDriver::new(&mut out).emit(0u64).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::OutOfRange);
+
+    let value: Wrapping<u8> = deserialize(vec![255u64.into()]);
+    assert_eq!(value, Wrapping(255));
+    let value: Reverse<u8> = deserialize(vec![1u64.into()]);
+    assert_eq!(value, Reverse(1));
+    let value: PhantomData<String> = deserialize(vec![Event::Atom(Atom::Null)]);
+    assert_eq!(value, PhantomData);
+
+    let range = || {
+        vec![
+            Event::MapStart,
+            "start".into(),
+            1u64.into(),
+            "end".into(),
+            5u64.into(),
+            Event::MapEnd,
+        ]
+    };
+    let value: Range<u32> = deserialize(range());
+    assert_eq!(value, 1..5);
+    let value: RangeInclusive<u32> = deserialize(range());
+    assert_eq!(value, 1..=5);
+
+    let value: Bound<u32> = deserialize(vec!["Unbounded".into()]);
+    assert_eq!(value, Bound::Unbounded);
+    let value: Bound<u32> = deserialize(vec![
+        Event::MapStart,
+        "Excluded".into(),
+        3u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(value, Bound::Excluded(3));
+
+    let value: Result<u32, String> = deserialize(vec![
+        Event::MapStart,
+        "Err".into(),
+        "failed".into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(value, Err("failed".into()));
+
+    let mut out = None::<Result<u32, String>>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    driver.emit(Event::MapStart).unwrap();
+    driver.emit("Okay").unwrap();
+    let err = driver.emit(1u64).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unexpected: unknown variant \"Okay\", expected one of \"Ok\", \"Err\""
+    );
+}
+
+#[test]
+fn test_bytes() {
+    use deser::{ByteBuf, Bytes};
+
+    let value: ByteBuf = deserialize(vec![Event::Atom(Atom::Bytes(Cow::Borrowed(b"abc")))]);
+    assert_eq!(value.into_vec(), b"abc".to_vec());
+    let value: ByteBuf = deserialize(vec!["abc".into()]);
+    assert_eq!(&value[..], b"abc");
+    let value: Bytes = deserialize(vec![
+        Event::SeqStart,
+        97u64.into(),
+        98u64.into(),
+        Event::SeqEnd,
+    ]);
+    assert_eq!(&*value, b"ab");
+
+    let mut out = None::<ByteBuf>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    driver.emit(Event::SeqStart).unwrap();
+    let err = driver.emit(256u64).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::OutOfRange);
+}
+
+#[test]
+fn test_string_map_keys() {
+    let map: HashMap<u32, u32> = deserialize(vec![
+        Event::MapStart,
+        Event::Atom(Atom::Str("1".into())),
+        Event::Atom(Atom::U64(2)),
+        Event::Atom(Atom::Str("3".into())),
+        Event::Atom(Atom::U64(4)),
+        Event::MapEnd,
+    ]);
+    assert_eq!(map, vec![(1, 2), (3, 4)].into_iter().collect());
+
+    let map: BTreeMap<bool, i8> = deserialize(vec![
+        Event::MapStart,
+        Event::Atom(Atom::Str("true".into())),
+        Event::Atom(Atom::I64(-1)),
+        Event::MapEnd,
+    ]);
+    assert_eq!(map, vec![(true, -1)].into_iter().collect());
+
+    let map: BTreeMap<char, f32> = deserialize(vec![
+        Event::MapStart,
+        Event::Atom(Atom::Str("x".into())),
+        Event::Atom(Atom::F64(1.5)),
+        Event::MapEnd,
+    ]);
+    assert_eq!(map.get(&'x'), Some(&1.5));
+
+    let mut out = None::<HashMap<u32, u32>>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    driver.emit(Event::MapStart).unwrap();
+    let err = driver
+        .emit(Event::Atom(Atom::Str("nope".into())))
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::Unexpected);
+    assert_eq!(err.to_string(), "Unexpected: invalid u32 key 'nope'");
+
+    // strings are still rejected outside of key position
+    let mut out = None::<HashMap<String, u32>>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    driver.emit(Event::MapStart).unwrap();
+    driver.emit(Event::Atom(Atom::Str("a".into()))).unwrap();
+    assert!(driver.emit(Event::Atom(Atom::Str("1".into()))).is_err());
+}
+
+#[test]
+fn test_lenient_coercion() {
+    fn lenient<T: Deserialize>(events: Vec<Event<'_>>) -> Result<T, deser::Error> {
+        let mut out = None;
+        {
+            let mut driver = DeserializeDriver::new(&mut out);
+            *driver.state().get_mut::<Coercion>() = Coercion::Lenient;
+            for event in events {
+                driver.emit(event)?;
+            }
+        }
+        Ok(out.unwrap())
+    }
+
+    let value: (i32, f32, char, Option<u8>, Option<u8>) = lenient(vec![
+        Event::SeqStart,
+        Event::Atom(Atom::Str("-7".into())),
+        Event::Atom(Atom::Str("2.5".into())),
+        Event::Atom(Atom::Str("x".into())),
+        Event::Atom(Atom::Str("".into())),
+        Event::Atom(Atom::Str("3".into())),
+        Event::SeqEnd,
+    ])
+    .unwrap();
+    assert_eq!(value, (-7, 2.5, 'x', None, Some(3)));
+
+    let err = lenient::<u8>(vec![Event::Atom(Atom::Str("300".into()))]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::OutOfRange);
+    assert_eq!(
+        err.to_string(),
+        "OutOfRange: u8 value '300' out of range for type"
+    );
+
+    let err = lenient::<i8>(vec![Event::Atom(Atom::Str("-200".into()))]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::OutOfRange);
+
+    let err = lenient::<u8>(vec![Event::Atom(Atom::Str("x".into()))]).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected: invalid u8 value 'x'");
+
+    let value = lenient::<std::num::NonZeroU32>(vec![Event::Atom(Atom::Str("7".into()))]).unwrap();
+    assert_eq!(value.get(), 7);
+    let err =
+        lenient::<std::num::NonZeroU32>(vec![Event::Atom(Atom::Str("0".into()))]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::OutOfRange);
+
+    let err = lenient::<bool>(vec![Event::Atom(Atom::Str("yes".into()))]).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected: invalid bool value 'yes'");
+
+    // strict by default
+    let mut out = None::<u32>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    assert!(driver.emit(Event::Atom(Atom::Str("42".into()))).is_err());
+}
+
+#[test]
+fn test_expected() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        name: String,
+        port: Option<u16>,
+        ratio: f32,
+        debug: bool,
+        tags: Vec<String>,
+        extra: HashMap<String, u32>,
+    }
+
+    let mut out = None::<Config>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    let descriptor = driver.expected().unwrap();
+    assert_eq!(descriptor.name(), Some("Config"));
+    assert_eq!(descriptor.kind(), Kind::Map);
+    driver.emit(Event::MapStart).unwrap();
+
+    fn check(driver: &mut DeserializeDriver, key: &str, kind: Kind) {
+        assert_eq!(driver.expected().unwrap().kind(), Kind::String);
+        driver.emit(key).unwrap();
+        assert_eq!(driver.expected().unwrap().kind(), kind);
+    }
+    check(&mut driver, "name", Kind::String);
+    driver.emit("x").unwrap();
+    check(&mut driver, "port", Kind::Int);
+    driver.emit(80u64).unwrap();
+    check(&mut driver, "ratio", Kind::Float);
+    driver.emit(1.0f64).unwrap();
+    check(&mut driver, "debug", Kind::Bool);
+    driver.emit(true).unwrap();
+    check(&mut driver, "tags", Kind::Seq);
+    driver.emit(Event::SeqStart).unwrap();
+    assert_eq!(driver.expected().unwrap().kind(), Kind::String);
+    driver.emit(Event::SeqEnd).unwrap();
+    check(&mut driver, "extra", Kind::Map);
+    driver.emit(Event::MapStart).unwrap();
+    driver.emit(Event::MapEnd).unwrap();
+    assert_eq!(driver.expected().unwrap().kind(), Kind::String);
+    driver.emit(Event::MapEnd).unwrap();
+    drop(driver);
+
+    let config = out.unwrap();
+    assert_eq!(config.port, Some(80));
+    assert!(config.tags.is_empty());
+}
+
+#[test]
+fn test_expected_before_end() {
+    #[derive(Debug, PartialEq)]
+    struct FinishedAsKey(bool);
+
+    make_slot_wrapper!(SlotWrapper);
+
+    impl Deserialize for FinishedAsKey {
+        fn deserialize_into(out: &mut Option<Self>) -> SinkHandle<'_> {
+            SlotWrapper::make_handle(out)
+        }
+    }
+
+    impl Sink for SlotWrapper<FinishedAsKey> {
+        fn map(&mut self, _state: &deser::de::DeserializerState) -> Result<(), deser::Error> {
+            Ok(())
+        }
+
+        fn next_key(
+            &mut self,
+            _state: &deser::de::DeserializerState,
+        ) -> Result<SinkHandle<'_>, deser::Error> {
+            Ok(SinkHandle::null())
+        }
+
+        fn finish(&mut self, state: &deser::de::DeserializerState) -> Result<(), deser::Error> {
+            **self = Some(FinishedAsKey(state.is_key()));
+            Ok(())
+        }
+    }
+
+    let mut out = None::<FinishedAsKey>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    driver.emit(Event::MapStart).unwrap();
+    assert_eq!(driver.expected().unwrap().kind(), Kind::Any);
+    driver.emit(Event::MapEnd).unwrap();
+    drop(driver);
+    assert_eq!(out, Some(FinishedAsKey(false)));
+
+    let mut out = None::<HashMap<String, u32>>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    driver.emit(Event::MapStart).unwrap();
+    driver.emit("a").unwrap();
+    driver.emit(1u64).unwrap();
+    assert_eq!(driver.expected().unwrap().kind(), Kind::String);
+    driver.emit(Event::MapEnd).unwrap();
+    assert!(!driver.state().is_key());
+    drop(driver);
+    assert_eq!(out.unwrap().get("a"), Some(&1));
+}
@@ -12,6 +12,17 @@ fn deserialize<T: Deserialize>(events: Vec<Event<'_>>) -> T {
     out.unwrap()
 }
 
+fn deserialize_err<T: Deserialize>(events: Vec<Event<'_>>) -> deser::Error {
+    let mut out = None::<T>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    for event in events {
+        if let Err(err) = driver.emit(event) {
+            return err;
+        }
+    }
+    panic!("expected an error");
+}
+
 #[test]
 fn test_container_defaults() {
     #[derive(Deserialize, Default)]
@@ -411,6 +422,10 @@ fn test_variant_alias() {
 
     let s: Stuff = deserialize(vec!["alpha".into()]);
     assert_eq!(s, Stuff::A);
+
+    let mut out = None::<Stuff>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    assert_eq!(driver.expected().unwrap().kind(), deser::Kind::String);
 }
 
 #[test]
@@ -507,3 +522,919 @@ fn test_deserializing_newtype() {
 
     assert_eq!(x.0, 1);
 }
+
+#[test]
+fn test_variant_other() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[deser(rename_all = "snake_case")]
+    enum Status {
+        Active,
+        #[deser(other)]
+        Unknown,
+    }
+
+    let s: Status = deserialize(vec!["active".into()]);
+    assert_eq!(s, Status::Active);
+    let s: Status = deserialize(vec!["suspended".into()]);
+    assert_eq!(s, Status::Unknown);
+}
+
+#[test]
+fn test_variant_other_capturing() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[deser(rename_all = "snake_case")]
+    enum Status {
+        Active,
+        #[deser(other)]
+        Unknown(String),
+    }
+
+    let s: Status = deserialize(vec!["active".into()]);
+    assert_eq!(s, Status::Active);
+    let s: Status = deserialize(vec!["suspended".into()]);
+    assert_eq!(s, Status::Unknown("suspended".into()));
+}
+
+#[test]
+fn test_enum_int_repr() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[deser(repr = "int")]
+    enum Level {
+        Low = 1,
+        High = 10,
+        Negative = -1,
+    }
+
+    let s: Level = deserialize(vec![1u64.into()]);
+    assert_eq!(s, Level::Low);
+    let s: Level = deserialize(vec![10i64.into()]);
+    assert_eq!(s, Level::High);
+    let s: Level = deserialize(vec![(-1i64).into()]);
+    assert_eq!(s, Level::Negative);
+
+    let mut out = None::<Level>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    let descriptor = driver.expected().unwrap();
+    assert_eq!(descriptor.name(), Some("Level"));
+    assert_eq!(descriptor.kind(), deser::Kind::Int);
+}
+
+#[test]
+#[should_panic = "unexpected value for enum"]
+fn test_enum_int_repr_unknown() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[deser(repr = "int")]
+    enum Level {
+        Low,
+        High,
+    }
+
+    let _: Level = deserialize(vec![2u64.into()]);
+}
+
+#[test]
+#[should_panic = "unexpected string"]
+fn test_enum_int_repr_rejects_names() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[deser(repr = "int")]
+    enum Level {
+        Low,
+        High,
+    }
+
+    let _: Level = deserialize(vec!["Low".into()]);
+}
+
+#[test]
+fn test_enum_int_or_name_repr() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[deser(repr = "int_or_name", rename_all = "lowercase")]
+    enum Level {
+        Low,
+        High,
+        #[deser(other)]
+        Unknown,
+    }
+
+    let s: Level = deserialize(vec!["high".into()]);
+    assert_eq!(s, Level::High);
+    let s: Level = deserialize(vec![1u64.into()]);
+    assert_eq!(s, Level::High);
+    let s: Level = deserialize(vec![42u64.into()]);
+    assert_eq!(s, Level::Unknown);
+
+    let mut out = None::<Level>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    assert_eq!(driver.expected().unwrap().kind(), deser::Kind::Any);
+}
+
+#[test]
+fn test_rename_per_direction() {
+    #[derive(Deserialize)]
+    #[deser(rename_all(deserialize = "camelCase"))]
+    struct Test {
+        #[deser(rename(serialize = "newName", deserialize = "oldName"))]
+        value: usize,
+        other_value: usize,
+    }
+
+    let s: Test = deserialize(vec![
+        Event::MapStart,
+        "oldName".into(),
+        1u64.into(),
+        "otherValue".into(),
+        2u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.value, 1);
+    assert_eq!(s.other_value, 2);
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Stuff {
+        #[deser(rename(deserialize = "a"))]
+        A,
+    }
+
+    let s: Stuff = deserialize(vec!["a".into()]);
+    assert_eq!(s, Stuff::A);
+}
+
+mod facade {
+    pub use deser;
+}
+
+#[test]
+fn test_crate_path() {
+    #[derive(Deserialize)]
+    #[deser(crate = "crate::facade::deser")]
+    struct Test {
+        value: usize,
+    }
+
+    let s: Test = deserialize(vec![
+        Event::MapStart,
+        "value".into(),
+        42u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.value, 42);
+}
+
+#[test]
+fn test_custom_bound() {
+    trait Config {
+        type Value;
+    }
+
+    struct Numbers;
+
+    impl Config for Numbers {
+        type Value = u32;
+    }
+
+    #[derive(Deserialize)]
+    #[deser(bound(deserialize = "C::Value: Deserialize"))]
+    struct Holder<C: Config> {
+        value: C::Value,
+    }
+
+    let s: Holder<Numbers> = deserialize(vec![
+        Event::MapStart,
+        "value".into(),
+        42u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.value, 42);
+}
+
+#[test]
+fn test_flatten_map() {
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Deserialize)]
+    struct Test {
+        a: usize,
+        #[deser(flatten)]
+        inner: Inner,
+        #[deser(flatten)]
+        extra: BTreeMap<String, usize>,
+    }
+
+    #[derive(Deserialize)]
+    struct Inner {
+        b: usize,
+    }
+
+    let s: Test = deserialize(vec![
+        Event::MapStart,
+        "a".into(),
+        1u64.into(),
+        "x".into(),
+        23u64.into(),
+        "b".into(),
+        2u64.into(),
+        "y".into(),
+        42u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.a, 1);
+    assert_eq!(s.inner.b, 2);
+    assert_eq!(
+        s.extra.into_iter().collect::<Vec<_>>(),
+        vec![("x".to_string(), 23), ("y".to_string(), 42)]
+    );
+
+    #[derive(Deserialize)]
+    struct Test2 {
+        a: usize,
+        #[deser(flatten)]
+        extra: HashMap<String, usize>,
+    }
+
+    let s: Test2 = deserialize(vec![
+        Event::MapStart,
+        "a".into(),
+        1u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.a, 1);
+    assert!(s.extra.is_empty());
+}
+
+#[test]
+fn test_flatten_map_before_struct() {
+    use std::collections::BTreeMap;
+
+    #[derive(Deserialize)]
+    struct Test {
+        #[deser(flatten)]
+        extra: BTreeMap<String, usize>,
+        #[deser(flatten)]
+        other: Option<Other>,
+    }
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct Other {
+        b: usize,
+    }
+
+    let s: Test = deserialize(vec![
+        Event::MapStart,
+        "b".into(),
+        1u64.into(),
+        "c".into(),
+        2u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.other, Some(Other { b: 1 }));
+    assert_eq!(
+        s.extra.into_iter().collect::<Vec<_>>(),
+        vec![("c".to_string(), 2)]
+    );
+}
+
+#[test]
+fn test_option_struct() {
+    #[derive(Deserialize)]
+    struct Inner {
+        a: usize,
+    }
+
+    let s: Option<Inner> = deserialize(vec![
+        Event::MapStart,
+        "a".into(),
+        1u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.unwrap().a, 1);
+    let s: Option<Inner> = deserialize(vec![Event::Atom(deser::Atom::Null)]);
+    assert!(s.is_none());
+}
+
+#[test]
+fn test_flatten_option() {
+    #[derive(Deserialize)]
+    struct Test {
+        a: usize,
+        #[deser(flatten)]
+        tls: Option<TlsConfig>,
+    }
+
+    #[derive(Deserialize)]
+    struct TlsConfig {
+        cert: String,
+        key: String,
+    }
+
+    let s: Test = deserialize(vec![
+        Event::MapStart,
+        "a".into(),
+        1u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.a, 1);
+    assert!(s.tls.is_none());
+
+    let s: Test = deserialize(vec![
+        Event::MapStart,
+        "a".into(),
+        1u64.into(),
+        "cert".into(),
+        "cert.pem".into(),
+        "key".into(),
+        "key.pem".into(),
+        Event::MapEnd,
+    ]);
+    let tls = s.tls.unwrap();
+    assert_eq!(tls.cert, "cert.pem");
+    assert_eq!(tls.key, "key.pem");
+}
+
+#[test]
+#[should_panic = "Missing field 'key'"]
+fn test_flatten_option_incomplete() {
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct Test {
+        #[deser(flatten)]
+        tls: Option<TlsConfig>,
+    }
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct TlsConfig {
+        cert: String,
+        key: String,
+    }
+
+    let _: Test = deserialize(vec![
+        Event::MapStart,
+        "cert".into(),
+        "cert.pem".into(),
+        Event::MapEnd,
+    ]);
+}
+
+#[test]
+fn test_flatten_prefix() {
+    #[derive(Deserialize)]
+    struct Test {
+        host: String,
+        #[deser(flatten(prefix = "db_"))]
+        db: DbConfig,
+    }
+
+    #[derive(Deserialize)]
+    struct DbConfig {
+        host: String,
+        port: u16,
+    }
+
+    let s: Test = deserialize(vec![
+        Event::MapStart,
+        "host".into(),
+        "example.com".into(),
+        "db_host".into(),
+        "localhost".into(),
+        "db_port".into(),
+        5432u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.host, "example.com");
+    assert_eq!(s.db.host, "localhost");
+    assert_eq!(s.db.port, 5432);
+}
+
+#[test]
+fn test_duplicate_fields() {
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct Test {
+        #[deser(alias = "ident")]
+        id: usize,
+    }
+
+    let err = deserialize_err::<Test>(vec![
+        Event::MapStart,
+        "id".into(),
+        1u64.into(),
+        "id".into(),
+        2u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(err.kind(), deser::ErrorKind::DuplicateField);
+    assert_eq!(err.to_string(), "DuplicateField: Duplicate field 'id'");
+
+    let err = deserialize_err::<Test>(vec![
+        Event::MapStart,
+        "id".into(),
+        1u64.into(),
+        "ident".into(),
+        2u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(err.kind(), deser::ErrorKind::DuplicateField);
+
+    #[derive(Deserialize)]
+    #[deser(allow_duplicate_fields)]
+    struct Lenient {
+        id: usize,
+    }
+
+    let s: Lenient = deserialize(vec![
+        Event::MapStart,
+        "id".into(),
+        1u64.into(),
+        "id".into(),
+        2u64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.id, 2);
+}
+
+#[test]
+fn test_unknown_name_suggestions() {
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    #[deser(deny_unknown_fields)]
+    struct Config {
+        account: String,
+        region: String,
+    }
+
+    assert_eq!(
+        deserialize_err::<Config>(vec![Event::MapStart, "acount".into(), "x".into()]).to_string(),
+        "Unexpected: unknown field \"acount\", did you mean \"account\"?"
+    );
+    assert_eq!(
+        deserialize_err::<Config>(vec![Event::MapStart, "zone".into(), "x".into()]).to_string(),
+        "Unexpected: unknown field \"zone\", expected one of \"account\", \"region\""
+    );
+    assert_eq!(
+        deserialize_err::<Config>(vec![
+            Event::MapStart,
+            "account".into(),
+            "x".into(),
+            Event::MapEnd
+        ])
+        .to_string(),
+        "MissingField: Missing field 'region', expected fields are \"account\", \"region\""
+    );
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    #[deser(rename_all = "lowercase")]
+    enum Color {
+        Green,
+        Red,
+    }
+
+    assert_eq!(
+        deserialize_err::<Color>(vec!["gren".into()]).to_string(),
+        "Unexpected: unknown variant \"gren\", did you mean \"green\"?"
+    );
+    assert_eq!(
+        deserialize_err::<Color>(vec!["blue".into()]).to_string(),
+        "Unexpected: unknown variant \"blue\", expected one of \"green\", \"red\""
+    );
+}
+
+#[test]
+fn test_match_keys() {
+    #[derive(Deserialize)]
+    #[deser(match_keys = "case_insensitive")]
+    struct Insensitive {
+        account_holder: String,
+    }
+
+    let s: Insensitive = deserialize(vec![
+        Event::MapStart,
+        "Account_Holder".into(),
+        "Jane".into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.account_holder, "Jane");
+
+    #[derive(Deserialize)]
+    #[deser(match_keys = "normalized")]
+    struct Normalized {
+        #[deser(alias = "owner")]
+        account_holder: String,
+        #[deser(flatten)]
+        extra: std::collections::BTreeMap<String, String>,
+    }
+
+    for key in ["ACCOUNT-HOLDER", "accountHolder", "account_holder", "Owner"] {
+        let s: Normalized = deserialize(vec![
+            Event::MapStart,
+            key.into(),
+            "Jane".into(),
+            "Other_Key".into(),
+            "x".into(),
+            Event::MapEnd,
+        ]);
+        assert_eq!(s.account_holder, "Jane");
+        assert_eq!(s.extra.keys().collect::<Vec<_>>(), vec!["Other_Key"]);
+    }
+}
+
+#[test]
+fn test_validation() {
+    fn no_spaces(value: &str) -> Result<(), deser::Error> {
+        if value.contains(' ') {
+            Err(deser::Error::new(
+                deser::ErrorKind::Unexpected,
+                "must not contain spaces",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_server(server: &Server) -> Result<(), deser::Error> {
+        if server.port == 80 && server.tags.contains(&"tls".to_string()) {
+            Err(deser::Error::new(
+                deser::ErrorKind::Unexpected,
+                "tls cannot be used on port 80",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[deser(validate = "check_server")]
+    struct Server {
+        #[deser(non_empty, validate = "no_spaces")]
+        name: String,
+        #[deser(range(min = 1, max = 65535))]
+        port: u32,
+        #[deser(length(max = 2))]
+        tags: Vec<String>,
+    }
+
+    let s: Server = deserialize(vec![
+        Event::MapStart,
+        "name".into(),
+        "web".into(),
+        "port".into(),
+        443u64.into(),
+        "tags".into(),
+        Event::SeqStart,
+        "tls".into(),
+        Event::SeqEnd,
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.name, "web");
+
+    let err = deserialize_err::<Server>(vec![
+        Event::MapStart,
+        "name".into(),
+        "".into(),
+        "port".into(),
+        0u64.into(),
+        "tags".into(),
+        Event::SeqStart,
+        Event::SeqEnd,
+        Event::MapEnd,
+    ]);
+    assert_eq!(err.kind(), deser::ErrorKind::WrongLength);
+    assert_eq!(
+        err.to_string(),
+        "WrongLength: invalid field 'name': value must not be empty; \
+         invalid field 'port': value must be between 1 and 65535, got 0"
+    );
+
+    let err = deserialize_err::<Server>(vec![
+        Event::MapStart,
+        "name".into(),
+        "my web".into(),
+        "port".into(),
+        80u64.into(),
+        "tags".into(),
+        Event::SeqStart,
+        "tls".into(),
+        "a".into(),
+        "b".into(),
+        Event::SeqEnd,
+        Event::MapEnd,
+    ]);
+    assert_eq!(
+        err.to_string(),
+        "Unexpected: invalid field 'name': must not contain spaces; \
+         invalid field 'tags': length must be at most 2, got 3; \
+         tls cannot be used on port 80"
+    );
+
+    #[derive(Deserialize, Debug)]
+    struct Ratio {
+        #[deser(range(min = 0, max = 1.5))]
+        value: f64,
+    }
+
+    let r: Ratio = deserialize(vec![
+        Event::MapStart,
+        "value".into(),
+        0.5f64.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(r.value, 0.5);
+
+    let err = deserialize_err::<Ratio>(vec![
+        Event::MapStart,
+        "value".into(),
+        (-1.0f64).into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(
+        err.to_string(),
+        "OutOfRange: invalid field 'value': value must be between 0 and 1.5, got -1"
+    );
+
+    let err = deserialize_err::<Ratio>(vec![
+        Event::MapStart,
+        "value".into(),
+        f64::NAN.into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(err.kind(), deser::ErrorKind::OutOfRange);
+}
+
+mod remote {
+    pub struct Duration {
+        pub secs: u64,
+        pub nanos: u32,
+    }
+}
+
+#[test]
+fn test_remote() {
+    #[derive(Deserialize)]
+    #[deser(remote = "remote::Duration")]
+    struct DurationDef {
+        secs: u64,
+        #[deser(default)]
+        nanos: u32,
+    }
+
+    #[derive(Deserialize)]
+    struct Timeout {
+        #[deser(with = "DurationDef")]
+        duration: remote::Duration,
+    }
+
+    let s: Timeout = deserialize(vec![
+        Event::MapStart,
+        "duration".into(),
+        Event::MapStart,
+        "secs".into(),
+        30u64.into(),
+        Event::MapEnd,
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.duration.secs, 30);
+    assert_eq!(s.duration.nanos, 0);
+}
+
+#[test]
+fn test_as_seq() {
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    #[deser(as_seq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let p: Point = deserialize(vec![
+        Event::SeqStart,
+        1u64.into(),
+        (-2i64).into(),
+        Event::SeqEnd,
+    ]);
+    assert_eq!(p, Point { x: 1, y: -2 });
+
+    let err = deserialize_err::<Point>(vec![Event::SeqStart, 1u64.into(), Event::SeqEnd]);
+    assert_eq!(err.kind(), deser::ErrorKind::WrongLength);
+    let err =
+        deserialize_err::<Point>(vec![Event::SeqStart, 1u64.into(), 2u64.into(), 3u64.into()]);
+    assert_eq!(err.kind(), deser::ErrorKind::WrongLength);
+    let err = deserialize_err::<Point>(vec![Event::MapStart]);
+    assert_eq!(err.kind(), deser::ErrorKind::Unexpected);
+}
+
+#[test]
+fn test_as_string() {
+    use std::error::Error as _;
+    use std::net::Ipv4Addr;
+    use std::num::ParseIntError;
+    use std::str::FromStr;
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    #[deser(as_string)]
+    struct Version {
+        major: u32,
+        minor: u32,
+    }
+
+    impl FromStr for Version {
+        type Err = ParseIntError;
+
+        fn from_str(s: &str) -> Result<Version, ParseIntError> {
+            let (major, minor) = s.split_once('.').unwrap_or((s, "0"));
+            Ok(Version {
+                major: major.parse()?,
+                minor: minor.parse()?,
+            })
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Server {
+        version: Version,
+        #[deser(with_string)]
+        addr: Ipv4Addr,
+    }
+
+    let s: Server = deserialize(vec![
+        Event::MapStart,
+        "version".into(),
+        "1.2".into(),
+        "addr".into(),
+        "127.0.0.1".into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(s.version, Version { major: 1, minor: 2 });
+    assert_eq!(s.addr, Ipv4Addr::LOCALHOST);
+
+    let mut out = None::<Version>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    assert_eq!(driver.expected().unwrap().name(), Some("Version"));
+    let err = driver.emit("1.x").unwrap_err();
+    assert_eq!(err.kind(), deser::ErrorKind::Unexpected);
+    assert_eq!(err.to_string(), "Unexpected: invalid value");
+    assert!(err.source().unwrap().is::<ParseIntError>());
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    #[deser(as_string)]
+    struct Tag<T> {
+        value: T,
+    }
+
+    impl<T: FromStr> FromStr for Tag<T> {
+        type Err = T::Err;
+
+        fn from_str(s: &str) -> Result<Tag<T>, T::Err> {
+            let value = s.strip_prefix('#').unwrap_or(s).parse()?;
+            Ok(Tag { value })
+        }
+    }
+
+    let tag: Tag<u32> = deserialize(vec!["#42".into()]);
+    assert_eq!(tag, Tag { value: 42 });
+    let mut out = None::<Tag<u32>>;
+    let mut driver = DeserializeDriver::new(&mut out);
+    let err = driver.emit("#x").unwrap_err();
+    assert!(err.source().unwrap().is::<ParseIntError>());
+}
+
+#[test]
+fn test_missing_value() {
+    use deser::de::SinkHandle;
+
+    #[derive(PartialEq, Eq, Debug)]
+    struct Tags(Vec<String>);
+
+    impl Deserialize for Tags {
+        fn deserialize_into(out: &mut Option<Self>) -> SinkHandle<'_> {
+            // reuse the vector sink and wrap it up on finish
+            struct TagsSink<'a> {
+                slot: &'a mut Option<Tags>,
+                inner: deser::de::OwnedSink<Vec<String>>,
+            }
+            impl<'a> deser::de::Sink for TagsSink<'a> {
+                fn seq(
+                    &mut self,
+                    state: &deser::de::DeserializerState,
+                ) -> Result<(), deser::Error> {
+                    self.inner.borrow_mut().seq(state)
+                }
+                fn next_value(
+                    &mut self,
+                    state: &deser::de::DeserializerState,
+                ) -> Result<SinkHandle<'_>, deser::Error> {
+                    self.inner.borrow_mut().next_value(state)
+                }
+                fn finish(
+                    &mut self,
+                    state: &deser::de::DeserializerState,
+                ) -> Result<(), deser::Error> {
+                    self.inner.borrow_mut().finish(state)?;
+                    *self.slot = self.inner.take().map(Tags);
+                    Ok(())
+                }
+            }
+            SinkHandle::boxed(TagsSink {
+                slot: out,
+                inner: deser::de::OwnedSink::deserialize(),
+            })
+        }
+
+        fn missing_value() -> Option<Self> {
+            Some(Tags(Vec::new()))
+        }
+    }
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct Post {
+        title: String,
+        tags: Tags,
+        draft: Option<bool>,
+    }
+
+    let post: Post = deserialize(vec![
+        Event::MapStart,
+        "title".into(),
+        "Hello".into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(
+        post,
+        Post {
+            title: "Hello".into(),
+            tags: Tags(Vec::new()),
+            draft: None,
+        }
+    );
+
+    let post: Post = deserialize(vec![
+        Event::MapStart,
+        "title".into(),
+        "Hello".into(),
+        "tags".into(),
+        Event::SeqStart,
+        "a".into(),
+        Event::SeqEnd,
+        Event::MapEnd,
+    ]);
+    assert_eq!(post.tags, Tags(vec!["a".into()]));
+}
+
+#[test]
+fn test_missing_value_through_wrappers() {
+    use std::rc::Rc;
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct Draft(Option<bool>);
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct Post {
+        title: String,
+        boxed: Box<Option<u32>>,
+        shared: Rc<Option<u32>>,
+        draft: Draft,
+    }
+
+    let post: Post = deserialize(vec![
+        Event::MapStart,
+        "title".into(),
+        "Hello".into(),
+        Event::MapEnd,
+    ]);
+    assert_eq!(
+        post,
+        Post {
+            title: "Hello".into(),
+            boxed: Box::new(None),
+            shared: Rc::new(None),
+            draft: Draft(None),
+        }
+    );
+}
+
+#[test]
+fn test_patch() {
+    use deser::Patch;
+
+    #[derive(Deserialize, PartialEq, Eq, Debug)]
+    struct UserPatch {
+        name: Patch<String>,
+        email: Patch<String>,
+        age: Patch<u32>,
+    }
+
+    let patch: UserPatch = deserialize(vec![
+        Event::MapStart,
+        "name".into(),
+        "John".into(),
+        "email".into(),
+        Event::Atom(deser::Atom::Null),
+        Event::MapEnd,
+    ]);
+    assert_eq!(
+        patch,
+        UserPatch {
+            name: Patch::Value("John".into()),
+            email: Patch::Null,
+            age: Patch::Absent,
+        }
+    );
+
+    let mut email = Some("john@example.com".to_string());
+    patch.email.apply_to(&mut email);
+    assert_eq!(email, None);
+}
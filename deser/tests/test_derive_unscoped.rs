@@ -20,3 +20,82 @@ fn test_unscoped() {
         !*value
     }
 }
+
+#[no_implicit_prelude]
+#[allow(dead_code)]
+mod no_prelude {
+    use ::std::collections::HashMap;
+    use ::std::option::Option;
+    use ::std::string::String;
+    use ::std::vec::Vec;
+
+    #[derive(::deser::Serialize, ::deser::Deserialize)]
+    #[deser(rename_all = "camelCase", default, skip_serializing_optionals)]
+    pub struct Root {
+        flag: Option<bool>,
+        #[deser(range(min = 1))]
+        count: u32,
+        #[deser(alias = "tag_list")]
+        tags: Vec<String>,
+        #[deser(flatten)]
+        attrs: Attrs,
+        #[deser(flatten)]
+        extra: HashMap<String, u32>,
+    }
+
+    impl ::std::default::Default for Root {
+        fn default() -> Root {
+            Root {
+                flag: Option::None,
+                count: 1,
+                tags: Vec::new(),
+                attrs: Attrs { is_active: false },
+                extra: HashMap::new(),
+            }
+        }
+    }
+
+    #[derive(::deser::Serialize, ::deser::Deserialize)]
+    #[deser(deny_unknown_fields, match_keys = "normalized")]
+    pub struct Attrs {
+        #[deser(groups = "admin")]
+        is_active: bool,
+    }
+
+    #[derive(::deser::Serialize, ::deser::Deserialize)]
+    #[deser(as_string)]
+    pub struct Version(u32);
+
+    impl ::std::fmt::Display for Version {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            ::std::fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl ::std::str::FromStr for Version {
+        type Err = ::std::num::ParseIntError;
+
+        fn from_str(s: &str) -> ::std::result::Result<Version, Self::Err> {
+            ::std::result::Result::Ok(Version(s.parse()?))
+        }
+    }
+
+    #[derive(::deser::Serialize, ::deser::Deserialize)]
+    pub struct Wrapper(Option<u32>);
+
+    #[derive(::deser::Serialize, ::deser::Deserialize)]
+    #[deser(as_seq)]
+    pub struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(::deser::Serialize, ::deser::Deserialize)]
+    #[deser(repr = "int_or_name")]
+    pub enum Level {
+        Low,
+        High,
+        #[deser(other)]
+        Unknown,
+    }
+}
@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::collections::BTreeSet;
 
 use deser::ser::SerializeDriver;
-use deser::{Atom, Event, Serialize};
+use deser::{Atom, Event, Kind, Serialize};
 
 fn capture_events(s: &dyn Serialize) -> Vec<Event<'static>> {
     let mut events = Vec::new();
@@ -94,3 +94,214 @@ fn test_set() {
         vec![Event::SeqStart, "bar".into(), "foo".into(), Event::SeqEnd]
     );
 }
+
+#[test]
+fn test_shared_and_interior_mutability() {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex, RwLock};
+
+    let expected = vec![Event::SeqStart, 1u64.into(), 2u64.into(), Event::SeqEnd];
+
+    assert_eq!(capture_events(&Rc::new(vec![1u32, 2])), expected);
+    assert_eq!(capture_events(&Arc::new(vec![1u32, 2])), expected);
+    assert_eq!(capture_events(&RefCell::new(vec![1u32, 2])), expected);
+    assert_eq!(capture_events(&Mutex::new(vec![1u32, 2])), expected);
+    assert_eq!(capture_events(&RwLock::new(vec![1u32, 2])), expected);
+    assert_eq!(capture_events(&Cell::new(42u32)), vec![42u64.into()]);
+    assert_eq!(capture_events(&Cell::new((1u32, 2u32))), expected);
+
+    let rc = Rc::new(vec![1u32, 2]);
+    let weak = Rc::downgrade(&rc);
+    assert_eq!(capture_events(&weak), expected);
+    drop(rc);
+    assert!(weak.is_optional());
+    assert_eq!(capture_events(&weak), vec![Event::Atom(Atom::Null)]);
+
+    fn first_descriptor(s: &dyn Serialize) -> (Option<String>, Kind) {
+        let mut driver = SerializeDriver::new(s);
+        let (_, descriptor, _) = driver.next().unwrap().unwrap();
+        (descriptor.name().map(Into::into), descriptor.kind())
+    }
+    assert_eq!(
+        first_descriptor(&Mutex::new(vec![1u32])),
+        (Some("mutex".into()), Kind::Seq)
+    );
+    assert_eq!(
+        first_descriptor(&RwLock::new(vec![1u32])),
+        (Some("rw_lock".into()), Kind::Seq)
+    );
+    assert_eq!(
+        first_descriptor(&RefCell::new(1u32)),
+        (Some("ref_cell".into()), Kind::Int)
+    );
+    assert_eq!(
+        first_descriptor(&Cell::new(true)),
+        (Some("cell".into()), Kind::Bool)
+    );
+}
+
+#[test]
+fn test_borrowed_and_poisoned() {
+    use std::cell::RefCell;
+    use std::sync::{Arc, Mutex};
+
+    let cell = RefCell::new(vec![1u32]);
+    let _guard = cell.borrow_mut();
+    let mut driver = SerializeDriver::new(&cell);
+    let err = driver.next().err().unwrap();
+    assert_eq!(err.kind(), deser::ErrorKind::Unexpected);
+    assert_eq!(
+        err.to_string(),
+        "Unexpected: value is already mutably borrowed"
+    );
+
+    let mutex = Arc::new(Mutex::new(vec![1u32]));
+    let mutex2 = mutex.clone();
+    std::thread::spawn(move || {
+        let _guard = mutex2.lock().unwrap();
+        panic!("poison");
+    })
+    .join()
+    .unwrap_err();
+    let mut driver = SerializeDriver::new(&*mutex);
+    let err = driver.next().err().unwrap();
+    assert_eq!(err.to_string(), "Unexpected: lock is poisoned");
+}
+
+#[test]
+fn test_collections() {
+    use std::collections::{BinaryHeap, LinkedList, VecDeque};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    let expected = vec![Event::SeqStart, 1u64.into(), 2u64.into(), Event::SeqEnd];
+
+    assert_eq!(capture_events(&VecDeque::from(vec![1u32, 2])), expected);
+    assert_eq!(
+        capture_events(&vec![1u32, 2].into_iter().collect::<LinkedList<_>>()),
+        expected
+    );
+    assert_eq!(capture_events(&BinaryHeap::from(vec![1u32])).len(), 3);
+    assert_eq!(capture_events(&vec![1u32, 2].into_boxed_slice()), expected);
+    assert_eq!(capture_events(&Rc::<[u32]>::from(vec![1, 2])), expected);
+    assert_eq!(capture_events(&Cow::<[u32]>::Owned(vec![1, 2])), expected);
+    assert_eq!(
+        capture_events(&Arc::<str>::from("hello")),
+        vec![Event::from("hello")]
+    );
+
+    let bytes = vec![Event::Atom(Atom::Bytes(Cow::Borrowed(b"abc")))];
+    let mut deque = VecDeque::from(b"bc".to_vec());
+    deque.push_front(b'a');
+    assert_eq!(capture_events(&deque), bytes);
+    assert_eq!(capture_events(&b"abc".to_vec().into_boxed_slice()), bytes);
+}
+
+#[test]
+fn test_time_net_path() {
+    use std::net::{IpAddr, Ipv6Addr};
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    assert_eq!(
+        capture_events(&Duration::new(2, 500)),
+        vec![
+            Event::MapStart,
+            "secs".into(),
+            2u64.into(),
+            "nanos".into(),
+            500u64.into(),
+            Event::MapEnd,
+        ]
+    );
+    assert_eq!(
+        capture_events(&(UNIX_EPOCH + Duration::from_secs(60))),
+        vec![
+            Event::MapStart,
+            "secs_since_epoch".into(),
+            60u64.into(),
+            "nanos_since_epoch".into(),
+            0u64.into(),
+            Event::MapEnd,
+        ]
+    );
+    assert_eq!(
+        capture_events(&IpAddr::V6(Ipv6Addr::LOCALHOST)),
+        vec![Event::from("::1")]
+    );
+    assert_eq!(
+        capture_events(&PathBuf::from("/tmp/foo")),
+        vec![Event::from("/tmp/foo")]
+    );
+    assert_eq!(
+        capture_events(&Path::new("/tmp/foo")),
+        vec![Event::from("/tmp/foo")]
+    );
+
+    let mut driver = SerializeDriver::new(&IpAddr::V6(Ipv6Addr::LOCALHOST));
+    let (_, descriptor, _) = driver.next().unwrap().unwrap();
+    assert_eq!(descriptor.name(), Some("IpAddr"));
+}
+
+#[test]
+fn test_small_std_types() {
+    use std::cmp::Reverse;
+    use std::marker::PhantomData;
+    use std::num::{NonZeroU32, Wrapping};
+    use std::ops::Bound;
+
+    assert_eq!(
+        capture_events(&NonZeroU32::new(42).unwrap()),
+        vec![42u64.into()]
+    );
+    assert_eq!(capture_events(&Wrapping(1u8)), vec![1u64.into()]);
+    assert_eq!(capture_events(&Reverse(1u8)), vec![1u64.into()]);
+    assert_eq!(
+        capture_events(&PhantomData::<String>),
+        vec![Event::Atom(Atom::Null)]
+    );
+
+    let expected = vec![
+        Event::MapStart,
+        "start".into(),
+        1u64.into(),
+        "end".into(),
+        5u64.into(),
+        Event::MapEnd,
+    ];
+    assert_eq!(capture_events(&(1u32..5)), expected);
+    assert_eq!(capture_events(&(1u32..=5)), expected);
+
+    assert_eq!(
+        capture_events(&Bound::<u32>::Unbounded),
+        vec!["Unbounded".into()]
+    );
+    assert_eq!(
+        capture_events(&Bound::Included(1u32)),
+        vec![
+            Event::MapStart,
+            "Included".into(),
+            1u64.into(),
+            Event::MapEnd
+        ]
+    );
+    assert_eq!(
+        capture_events(&Ok::<u32, String>(1)),
+        vec![Event::MapStart, "Ok".into(), 1u64.into(), Event::MapEnd]
+    );
+}
+
+#[test]
+fn test_bytes() {
+    use deser::{ByteBuf, Bytes};
+
+    fn generic<T: Serialize>(value: T) -> Vec<Event<'static>> {
+        capture_events(&value)
+    }
+
+    let expected = vec![Event::Atom(Atom::Bytes(Cow::Borrowed(b"abc")))];
+    assert_eq!(capture_events(&Bytes::new(b"abc")), expected);
+    assert_eq!(capture_events(&ByteBuf::from(b"abc".to_vec())), expected);
+    assert_eq!(generic(Bytes::new(b"abc")), expected);
+}
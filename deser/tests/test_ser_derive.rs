@@ -245,3 +245,496 @@ fn test_serializing_newtype() {
 
     assert_eq!(serialize(&MyInt(42)), vec![42u64.into()]);
 }
+
+#[test]
+fn test_variant_other_capturing() {
+    #[derive(Serialize)]
+    #[deser(rename_all = "snake_case")]
+    enum Status {
+        Active,
+        #[deser(other)]
+        Unknown(String),
+    }
+
+    assert_eq!(serialize(&Status::Active), vec!["active".into()]);
+    assert_eq!(
+        serialize(&Status::Unknown("suspended".into())),
+        vec!["suspended".into()]
+    );
+}
+
+#[test]
+fn test_enum_int_repr() {
+    #[derive(Serialize)]
+    #[deser(repr = "int")]
+    enum Level {
+        Low = 1,
+        High = 10,
+        Negative = -1,
+    }
+
+    assert_eq!(serialize(&Level::Low), vec![1u64.into()]);
+    assert_eq!(serialize(&Level::High), vec![10u64.into()]);
+    assert_eq!(serialize(&Level::Negative), vec![(-1i64).into()]);
+    assert_eq!(Level::Low.descriptor().name(), Some("Level"));
+    assert_eq!(Level::Low.descriptor().kind(), deser::Kind::Int);
+}
+
+#[test]
+fn test_enum_name_or_int_repr() {
+    #[derive(Serialize)]
+    #[deser(repr = "name_or_int")]
+    enum Level {
+        Low,
+    }
+
+    assert_eq!(serialize(&Level::Low), vec!["Low".into()]);
+    assert_eq!(Level::Low.descriptor().kind(), deser::Kind::String);
+}
+
+#[test]
+fn test_rename_per_direction() {
+    #[derive(Serialize)]
+    #[deser(rename_all(serialize = "camelCase"))]
+    struct Test {
+        #[deser(rename(serialize = "newName", deserialize = "oldName"))]
+        value: usize,
+        other_value: usize,
+    }
+
+    assert_eq!(
+        serialize(&Test {
+            value: 1,
+            other_value: 2
+        }),
+        vec![
+            Event::MapStart,
+            "newName".into(),
+            1u64.into(),
+            "otherValue".into(),
+            2u64.into(),
+            Event::MapEnd,
+        ]
+    );
+
+    #[derive(Serialize)]
+    #[deser(rename_all(deserialize = "lowercase"))]
+    enum Stuff {
+        #[deser(rename(serialize = "alpha"))]
+        A,
+        B,
+    }
+
+    assert_eq!(serialize(&Stuff::A), vec!["alpha".into()]);
+    assert_eq!(serialize(&Stuff::B), vec!["B".into()]);
+}
+
+mod facade {
+    pub use deser;
+}
+
+#[test]
+fn test_crate_path() {
+    #[derive(Serialize)]
+    #[deser(crate = "crate::facade::deser")]
+    struct Test {
+        value: usize,
+    }
+
+    assert_eq!(
+        serialize(&Test { value: 42 }),
+        vec![Event::MapStart, "value".into(), 42u64.into(), Event::MapEnd,]
+    );
+}
+
+#[test]
+fn test_custom_bound() {
+    trait Config {
+        type Value;
+    }
+
+    struct Numbers;
+
+    impl Config for Numbers {
+        type Value = u32;
+    }
+
+    #[derive(Serialize)]
+    #[deser(bound = "C::Value: Serialize")]
+    struct Holder<C: Config> {
+        value: C::Value,
+    }
+
+    assert_eq!(
+        serialize(&Holder::<Numbers> { value: 42 }),
+        vec![Event::MapStart, "value".into(), 42u64.into(), Event::MapEnd,]
+    );
+}
+
+#[test]
+fn test_flatten_map() {
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Test {
+        a: usize,
+        #[deser(flatten)]
+        extra: BTreeMap<String, usize>,
+        b: usize,
+    }
+
+    let mut extra = BTreeMap::new();
+    extra.insert("x".to_string(), 23);
+    extra.insert("y".to_string(), 42);
+
+    assert_eq!(
+        serialize(&Test { a: 1, extra, b: 2 }),
+        vec![
+            Event::MapStart,
+            "a".into(),
+            1u64.into(),
+            "x".into(),
+            23u64.into(),
+            "y".into(),
+            42u64.into(),
+            "b".into(),
+            2u64.into(),
+            Event::MapEnd,
+        ]
+    );
+}
+
+#[test]
+fn test_flatten_option() {
+    #[derive(Serialize)]
+    struct Test {
+        a: usize,
+        #[deser(flatten)]
+        tls: Option<TlsConfig>,
+    }
+
+    #[derive(Serialize)]
+    struct TlsConfig {
+        cert: String,
+    }
+
+    assert_eq!(
+        serialize(&Test { a: 1, tls: None }),
+        vec![Event::MapStart, "a".into(), 1u64.into(), Event::MapEnd]
+    );
+    assert_eq!(
+        serialize(&Test {
+            a: 1,
+            tls: Some(TlsConfig {
+                cert: "cert.pem".into()
+            })
+        }),
+        vec![
+            Event::MapStart,
+            "a".into(),
+            1u64.into(),
+            "cert".into(),
+            "cert.pem".into(),
+            Event::MapEnd
+        ]
+    );
+}
+
+#[test]
+fn test_flatten_guarded() {
+    use std::cell::RefCell;
+    use std::sync::Mutex;
+
+    #[derive(Serialize)]
+    struct Test {
+        #[deser(flatten)]
+        locked: Mutex<Inner>,
+        #[deser(flatten)]
+        borrowed: RefCell<Other>,
+    }
+
+    #[derive(Serialize)]
+    struct Inner {
+        a: usize,
+    }
+
+    #[derive(Serialize)]
+    struct Other {
+        b: usize,
+    }
+
+    let test = Test {
+        locked: Mutex::new(Inner { a: 1 }),
+        borrowed: RefCell::new(Other { b: 2 }),
+    };
+    assert_eq!(
+        serialize(&test),
+        vec![
+            Event::MapStart,
+            "a".into(),
+            1u64.into(),
+            "b".into(),
+            2u64.into(),
+            Event::MapEnd
+        ]
+    );
+    assert!(test.locked.try_lock().is_ok());
+    assert!(test.borrowed.try_borrow_mut().is_ok());
+}
+
+#[test]
+fn test_flatten_prefix() {
+    #[derive(Serialize)]
+    struct Test {
+        host: String,
+        #[deser(flatten(prefix = "db_"))]
+        db: DbConfig,
+    }
+
+    #[derive(Serialize)]
+    struct DbConfig {
+        host: String,
+        port: u16,
+    }
+
+    assert_eq!(
+        serialize(&Test {
+            host: "example.com".into(),
+            db: DbConfig {
+                host: "localhost".into(),
+                port: 5432,
+            }
+        }),
+        vec![
+            Event::MapStart,
+            "host".into(),
+            "example.com".into(),
+            "db_host".into(),
+            "localhost".into(),
+            "db_port".into(),
+            5432u64.into(),
+            Event::MapEnd,
+        ]
+    );
+}
+
+mod remote {
+    pub struct Duration {
+        pub secs: u64,
+        pub nanos: u32,
+    }
+}
+
+#[test]
+fn test_remote() {
+    #[derive(Serialize)]
+    #[deser(remote = "remote::Duration")]
+    struct DurationDef {
+        secs: u64,
+        nanos: u32,
+    }
+
+    #[derive(Serialize)]
+    struct Timeout {
+        #[deser(with = "DurationDef")]
+        duration: remote::Duration,
+    }
+
+    assert_eq!(
+        serialize(&Timeout {
+            duration: remote::Duration { secs: 30, nanos: 5 }
+        }),
+        vec![
+            Event::MapStart,
+            "duration".into(),
+            Event::MapStart,
+            "secs".into(),
+            30u64.into(),
+            "nanos".into(),
+            5u64.into(),
+            Event::MapEnd,
+            Event::MapEnd,
+        ]
+    );
+}
+
+#[test]
+fn test_as_seq() {
+    #[derive(Serialize)]
+    #[deser(as_seq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    assert_eq!(
+        serialize(&Point { x: 1, y: -2 }),
+        vec![Event::SeqStart, 1i64.into(), (-2i64).into(), Event::SeqEnd,]
+    );
+}
+
+#[test]
+fn test_as_string() {
+    use std::fmt;
+    use std::net::Ipv4Addr;
+
+    #[derive(Serialize)]
+    #[deser(as_string)]
+    struct Version {
+        major: u32,
+        minor: u32,
+    }
+
+    impl fmt::Display for Version {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}.{}", self.major, self.minor)
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Server {
+        version: Version,
+        #[deser(with_string)]
+        addr: Ipv4Addr,
+    }
+
+    assert_eq!(
+        serialize(&Server {
+            version: Version { major: 1, minor: 2 },
+            addr: Ipv4Addr::LOCALHOST,
+        }),
+        vec![
+            Event::MapStart,
+            "version".into(),
+            "1.2".into(),
+            "addr".into(),
+            "127.0.0.1".into(),
+            Event::MapEnd,
+        ]
+    );
+
+    #[derive(Serialize)]
+    #[deser(as_string)]
+    struct Tag<T> {
+        value: T,
+    }
+
+    impl<T: fmt::Display> fmt::Display for Tag<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "#{}", self.value)
+        }
+    }
+
+    assert_eq!(serialize(&Tag { value: 42 }), vec!["#42".into()]);
+}
+
+#[test]
+fn test_groups() {
+    use deser::ser::Groups;
+
+    fn serialize_groups<T: Serialize>(value: &T, groups: Groups) -> Vec<Event<'static>> {
+        let mut rv = Vec::new();
+        let mut driver = SerializeDriver::new(value);
+        *driver.state().get_mut::<Groups>() = groups;
+        while let Some((event, _, _)) = driver.next().unwrap() {
+            rv.push(event.to_static());
+        }
+        rv
+    }
+
+    #[derive(Serialize)]
+    struct Audit {
+        #[deser(groups = "internal")]
+        created_by: String,
+    }
+
+    #[derive(Serialize)]
+    struct User {
+        id: u64,
+        #[deser(groups = "public, admin")]
+        name: String,
+        #[deser(groups = "admin")]
+        email: String,
+        #[deser(flatten, groups = "admin, internal")]
+        audit: Audit,
+    }
+
+    let user = User {
+        id: 1,
+        name: "John".into(),
+        email: "john@example.com".into(),
+        audit: Audit {
+            created_by: "root".into(),
+        },
+    };
+
+    assert_eq!(
+        serialize_groups(&user, Groups::new(["public"])),
+        vec![
+            Event::MapStart,
+            "id".into(),
+            1u64.into(),
+            "name".into(),
+            "John".into(),
+            Event::MapEnd,
+        ]
+    );
+    assert_eq!(
+        serialize_groups(&user, Groups::new(["admin"])),
+        vec![
+            Event::MapStart,
+            "id".into(),
+            1u64.into(),
+            "name".into(),
+            "John".into(),
+            "email".into(),
+            "john@example.com".into(),
+            Event::MapEnd,
+        ]
+    );
+    assert_eq!(
+        serialize_groups(&user, Groups::new(["admin", "internal"])),
+        vec![
+            Event::MapStart,
+            "id".into(),
+            1u64.into(),
+            "name".into(),
+            "John".into(),
+            "email".into(),
+            "john@example.com".into(),
+            "created_by".into(),
+            "root".into(),
+            Event::MapEnd,
+        ]
+    );
+    assert_eq!(serialize(&user).len(), 10);
+}
+
+#[test]
+fn test_patch() {
+    use deser::Patch;
+
+    #[derive(Serialize)]
+    #[deser(skip_serializing_optionals)]
+    struct UserPatch {
+        name: Patch<String>,
+        email: Patch<String>,
+        age: Patch<u32>,
+    }
+
+    assert_eq!(
+        serialize(&UserPatch {
+            name: Patch::Value("John".into()),
+            email: Patch::Null,
+            age: Patch::Absent,
+        }),
+        vec![
+            Event::MapStart,
+            "name".into(),
+            "John".into(),
+            "email".into(),
+            Event::Atom(deser::Atom::Null),
+            Event::MapEnd,
+        ]
+    );
+}